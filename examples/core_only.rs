@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+use bevy_mod_picking::{Pickable, PickingCamera, PickingCameraBundle, PickingPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(PickingPlugin) // <- Only finds what is under the cursor, without interaction.
+        .add_startup_system(setup)
+        .add_system(print_nearest)
+        .run();
+}
+
+/// Logs the nearest entity under the cursor whenever it changes.
+fn print_nearest(mut last: Local<Option<Entity>>, pick_source_query: Query<&PickingCamera>) {
+    let nearest = pick_source_query
+        .iter()
+        .find_map(|pick_source| pick_source.intersections().first())
+        .map(|(entity, intersection)| (*entity, intersection.position()));
+    if nearest.map(|(entity, _)| entity) != *last {
+        match nearest {
+            Some((entity, position)) => info!("{:?} at {}", entity, position),
+            None => info!("Nothing under the cursor"),
+        }
+        *last = nearest.map(|(entity, _)| entity);
+    }
+}
+
+/// set up a simple 3D scene
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        // Makes the mesh pickable, without the selection and highlighting of the PickableBundle.
+        .insert(Pickable);
+    // cube
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_xyz(0.0, 0.5, 0.0),
+            ..Default::default()
+        })
+        .insert(Pickable);
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+    // camera
+    commands
+        .spawn_bundle(Camera3dBundle {
+            transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..Default::default()
+        })
+        .insert_bundle(PickingCameraBundle::default()); // <- Sets the camera to use for picking.
+}
@@ -15,7 +15,17 @@ pub fn print_events(mut events: EventReader<PickingEvent>) {
         match event {
             PickingEvent::Selection(e) => info!("A selection event happened: {:?}", e),
             PickingEvent::Hover(e) => info!("Egads! A hover event!? {:?}", e),
-            PickingEvent::Clicked(e) => info!("Gee Willikers, it's a click! {:?}", e),
+            PickingEvent::Drag(e) => info!("What a drag: {:?}", e),
+            PickingEvent::Clicked { entity, hit } => info!(
+                "Gee Willikers, it's a click! {:?} at {:?}",
+                entity,
+                hit.as_ref().map(|hit| hit.position())
+            ),
+            PickingEvent::DoubleClicked { entity, .. } => {
+                info!("Double the clicks, double the fun! {:?}", entity)
+            }
+            PickingEvent::HoverTimeout(e) => info!("Still hovering, have a tooltip: {:?}", e),
+            PickingEvent::LongPressed { entity, .. } => info!("Hold it right there! {:?}", entity),
         }
     }
 }
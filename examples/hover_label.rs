@@ -0,0 +1,98 @@
+use bevy::prelude::*;
+use bevy_mod_picking::{
+    screen_position, DefaultPickingPlugins, PickableBundle, PickingCamera, PickingCameraBundle,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPickingPlugins) // <- Adds Picking, Interaction, and Highlighting plugins.
+        .add_startup_system(setup)
+        .add_system(move_label)
+        .run();
+}
+
+#[derive(Component)]
+struct HoverLabel;
+
+/// Places the label next to the point under the cursor, and hides it when nothing is hovered.
+fn move_label(
+    pick_source_query: Query<(&PickingCamera, &Camera, &GlobalTransform)>,
+    mut label_query: Query<(&mut Style, &mut Text, &mut Visibility), With<HoverLabel>>,
+) {
+    let (mut style, mut text, mut visibility) = label_query.single_mut();
+    let (pick_source, camera, camera_transform) = pick_source_query.single();
+    let hovered = pick_source
+        .intersections()
+        .first()
+        .and_then(|(entity, intersection)| {
+            let position = intersection.position();
+            screen_position(camera, camera_transform, position).map(|screen| (*entity, screen))
+        });
+    match hovered {
+        Some((entity, screen)) => {
+            style.position = UiRect {
+                left: Val::Px(screen.x + 10.0),
+                bottom: Val::Px(screen.y + 10.0),
+                ..Default::default()
+            };
+            text.sections[0].value = format!("{:?}", entity);
+            visibility.is_visible = true;
+        }
+        None => visibility.is_visible = false,
+    }
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    asset_server: Res<AssetServer>,
+) {
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+            material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+            ..Default::default()
+        })
+        .insert_bundle(PickableBundle::default()); // <- Makes the mesh pickable.
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+            transform: Transform::from_xyz(0.0, 0.5, 0.0),
+            ..Default::default()
+        })
+        .insert_bundle(PickableBundle::default()); // <- Makes the mesh pickable.
+    commands.spawn_bundle(PointLightBundle {
+        point_light: PointLight {
+            intensity: 1500.0,
+            shadows_enabled: true,
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+    commands
+        .spawn_bundle(Camera3dBundle {
+            transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+            ..Default::default()
+        })
+        .insert_bundle(PickingCameraBundle::default()); // <- Sets the camera to use for picking.
+    commands
+        .spawn_bundle(
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraSans-Bold.ttf"),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+            )
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                ..Default::default()
+            }),
+        )
+        .insert(HoverLabel);
+}
@@ -1,7 +1,7 @@
 use bevy::{prelude::*, window::PresentMode};
 use bevy_mod_picking::{
-    DebugCursorPickingPlugin, DebugEventsPickingPlugin, DefaultPickingPlugins, PickableBundle,
-    PickingCameraBundle,
+    DebugCursorPickingPlugin, DebugCursorSettings, DebugEventsPickingPlugin, DefaultPickingPlugins,
+    PickableBundle, PickingCameraBundle,
 };
 
 fn main() {
@@ -12,12 +12,19 @@ fn main() {
         })
         .add_plugins(DefaultPlugins)
         .add_plugins(DefaultPickingPlugins) // <- Adds Picking, Interaction, and Highlighting plugins.
-        .add_plugin(DebugCursorPickingPlugin) // <- Adds the green debug cursor.
+        .add_plugin(DebugCursorPickingPlugin) // <- Adds the debug cursor, toggled with D.
         .add_plugin(DebugEventsPickingPlugin) // <- Adds debug event logging.
         .add_startup_system(setup)
+        .add_system(toggle_debug_cursor)
         .run();
 }
 
+fn toggle_debug_cursor(keyboard: Res<Input<KeyCode>>, mut settings: ResMut<DebugCursorSettings>) {
+    if keyboard.just_pressed(KeyCode::D) {
+        settings.enabled = !settings.enabled;
+    }
+}
+
 /// set up a simple 3D scene
 fn setup(
     mut commands: Commands,
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use bevy_mod_picking::{
+    DebugEventsPickingPlugin, DefaultPickingPlugins, PickableBundle, PickableSprite,
+    PickingCameraBundle, SpritePickingPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(DefaultPickingPlugins) // <- Adds Picking, Interaction, and Highlighting plugins.
+        .add_plugin(SpritePickingPlugin) // <- Adds picking and highlighting for sprites.
+        .add_plugin(DebugEventsPickingPlugin) // <- Adds debug event logging.
+        .add_startup_system(setup)
+        .run();
+}
+
+/// set up a few overlapping sprites
+fn setup(mut commands: Commands) {
+    for (i, color) in [Color::PURPLE, Color::ORANGE, Color::TEAL]
+        .into_iter()
+        .enumerate()
+    {
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color,
+                    custom_size: Some(Vec2::splat(128.0)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(i as f32 * 64.0 - 64.0, 0.0, i as f32)
+                    .with_rotation(Quat::from_rotation_z(i as f32 * 0.3)),
+                ..default()
+            })
+            .insert_bundle(PickableBundle::default()) // <- Makes the sprite pickable.
+            .insert(PickableSprite); // <- Tests the sprite's quad instead of a mesh.
+    }
+    commands
+        .spawn_bundle(Camera2dBundle::default())
+        .insert_bundle(PickingCameraBundle::default()); // <- Sets the camera to use for picking.
+}
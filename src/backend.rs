@@ -0,0 +1,37 @@
+use crate::PickingCamera;
+use bevy::prelude::*;
+use bevy_mod_raycast::IntersectionData;
+use std::cmp::Ordering;
+
+/// Replaces the intersections a hit testing backend found for a [PickingCamera] last frame with the
+/// ones it found this frame, keeping the camera's intersections sorted by distance.
+///
+/// Besides the built-in mesh ray cast, any number of backends can add intersections to a
+/// [PickingCamera]. A backend is a system labeled [PickingSystem::Backend](crate::PickingSystem),
+/// that runs after [PickingSystem::UpdateIntersections](crate::PickingSystem) with the
+/// [UpdatePicksCriteria](crate::UpdatePicksCriteria) run criteria, and:
+///
+/// - casts the camera's [ray](PickingCamera::ray) against the entities it is responsible for,
+/// - only reports pickable entities, e.g. those with a [PickableBundle](crate::PickableBundle),
+/// - calls this function with `owns` returning `true` for each entity it is responsible for.
+///
+/// Intersections are then filtered, e.g. by [MaxPickDistance](crate::MaxPickDistance), regardless
+/// of which backend found them.
+pub fn replace_intersections(
+    pick_source: &mut PickingCamera,
+    owns: impl Fn(Entity) -> bool,
+    hits: Vec<(Entity, IntersectionData)>,
+) {
+    let intersections = pick_source.intersections_mut();
+    // Drop this backend's hits from previous frames, in case the mesh ray cast didn't run.
+    intersections.retain(|(entity, _)| !owns(*entity));
+    if hits.is_empty() {
+        return;
+    }
+    intersections.extend(hits);
+    intersections.sort_by(|a, b| {
+        a.1.distance()
+            .partial_cmp(&b.1.distance())
+            .unwrap_or(Ordering::Equal)
+    });
+}
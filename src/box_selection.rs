@@ -0,0 +1,209 @@
+use crate::{
+    mouse::cursor_in_viewport, selection::is_no_deselect, NoDeselect, PausedForBlockers,
+    PickingCamera, RayCastMethod, Selection, SelectionSettings,
+};
+use bevy::prelude::*;
+
+/// Resource that configures box selection, added by the
+/// [BoxSelectionPlugin](crate::BoxSelectionPlugin).
+#[derive(Clone, Debug, Resource)]
+pub struct BoxSelectionSettings {
+    /// The mouse button that starts a box when pressed anywhere but on an entity with a
+    /// [Selection].
+    pub button: MouseButton,
+    /// Boxes both narrower and shorter than this, in logical pixels, are treated as a normal
+    /// click. A box that is thin in one direction, e.g. dragged along a row of entities, still
+    /// selects.
+    pub min_size: f32,
+    /// Draw the box with a UI node while it is being dragged.
+    pub show_rect: bool,
+    /// The color of the box drawn when [`show_rect`](Self::show_rect) is enabled.
+    pub rect_color: Color,
+}
+
+impl Default for BoxSelectionSettings {
+    fn default() -> Self {
+        Self {
+            button: MouseButton::Left,
+            min_size: 5.0,
+            show_rect: true,
+            rect_color: Color::rgba(0.35, 0.55, 1.0, 0.25),
+        }
+    }
+}
+
+/// Tracks the box currently being dragged out by the mouse.
+#[derive(Debug, Default, Resource)]
+pub struct BoxSelection {
+    start: Option<Vec2>,
+    end: Vec2,
+}
+
+impl BoxSelection {
+    /// The bottom left and top right corners of the box being dragged, in logical pixels.
+    pub fn rect(&self) -> Option<(Vec2, Vec2)> {
+        self.start
+            .map(|start| (start.min(self.end), start.max(self.end)))
+    }
+}
+
+/// Marks the UI node that draws the [BoxSelection].
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct BoxSelectionRect;
+
+/// Starts a [BoxSelection] when the [BoxSelectionSettings] button is pressed anywhere but on an
+/// entity with a [Selection], e.g. on the ground, and selects every visible entity with a
+/// [Selection] whose position is inside the box when it is released. Holding one of the
+/// [SelectionSettings] multiselect keys adds to the current selection instead of replacing it.
+/// Replacing the selection doesn't deselect entities marked [NoDeselect], and
+/// [enforce_selection_groups](crate::enforce_selection_groups) runs afterwards, so at most one
+/// entity of each [SelectionGroup](crate::SelectionGroup) stays selected.
+///
+/// Entities are tested against the viewport of the camera the box was started in, so a box drawn
+/// in one half of a split-screen window only selects entities seen by that half's camera.
+///
+/// Runs after [mesh_selection](crate::mesh_selection), so a box overrides the click on empty space
+/// it started with.
+#[allow(clippy::too_many_arguments)]
+pub fn box_selection(
+    paused: Option<Res<PausedForBlockers>>,
+    settings: Res<BoxSelectionSettings>,
+    selection_settings: Res<SelectionSettings>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut box_selection: ResMut<BoxSelection>,
+    pick_source_query: Query<(&PickingCamera, &Camera, &GlobalTransform)>,
+    pressed_query: Query<&Interaction, With<Selection>>,
+    mut selection_query: Query<(
+        Entity,
+        &mut Selection,
+        &GlobalTransform,
+        Option<&ComputedVisibility>,
+    )>,
+    hierarchy_query: Query<(Option<&NoDeselect>, Option<&Parent>)>,
+) {
+    let cursor =
+        pick_source_query
+            .iter()
+            .find_map(|(pick_source, _, _)| match pick_source.cast_method {
+                RayCastMethod::Screenspace(cursor) => Some(cursor),
+                _ => None,
+            });
+    let cursor = match cursor {
+        Some(cursor) => cursor,
+        None => return,
+    };
+
+    if mouse_button_input.just_pressed(settings.button) {
+        let paused = paused.map_or(false, |paused| paused.is_paused());
+        let on_selectable = pressed_query
+            .iter()
+            .any(|interaction| *interaction != Interaction::None);
+        if !paused && !on_selectable {
+            box_selection.start = Some(cursor);
+        }
+    }
+    if box_selection.start.is_none() {
+        return;
+    }
+    box_selection.end = cursor;
+    if !mouse_button_input.just_released(settings.button) {
+        return;
+    }
+
+    let (start, (min, max)) = match box_selection.start.zip(box_selection.rect()) {
+        Some(rect) => rect,
+        None => return,
+    };
+    box_selection.start = None;
+    if (max - min).max_element() < settings.min_size {
+        return;
+    }
+    let (camera, camera_transform) = match pick_source_query
+        .iter()
+        .find(|(_, camera, _)| cursor_in_viewport(start, camera))
+    {
+        Some((_, camera, camera_transform)) => (camera, camera_transform),
+        None => return,
+    };
+    let viewport_origin = match viewport_origin(camera) {
+        Some(origin) => origin,
+        None => return,
+    };
+    let multiselect =
+        keyboard_input.any_pressed(selection_settings.multiselect_keys.iter().copied());
+    for (entity, mut selection, transform, visibility) in selection_query.iter_mut() {
+        let visible = visibility.map_or(true, |visibility| visibility.is_visible());
+        let inside = visible
+            && camera
+                .world_to_viewport(camera_transform, transform.translation())
+                .map_or(false, |position| {
+                    let position = position + viewport_origin;
+                    position.cmpge(min).all() && position.cmple(max).all()
+                });
+        if inside && !selection.selected() {
+            selection.set_selected(true);
+        } else if !inside
+            && !multiselect
+            && selection.selected()
+            && !is_no_deselect(entity, &hierarchy_query)
+        {
+            selection.set_selected(false);
+        }
+    }
+}
+
+/// The bottom left corner of the camera's viewport, in logical pixels with the origin in the bottom
+/// left of the render target, like the cursor. [Camera::world_to_viewport] positions are relative
+/// to this corner.
+fn viewport_origin(camera: &Camera) -> Option<Vec2> {
+    let target_size = camera.logical_target_size()?;
+    // The viewport rect has its origin in the top left of the render target.
+    let (viewport_min, viewport_max) = camera.logical_viewport_rect()?;
+    Some(Vec2::new(viewport_min.x, target_size.y - viewport_max.y))
+}
+
+/// Draws the [BoxSelection] with a UI node while it is being dragged.
+pub fn update_box_selection_rect(
+    mut commands: Commands,
+    settings: Res<BoxSelectionSettings>,
+    box_selection: Res<BoxSelection>,
+    mut rect_query: Query<(Entity, &mut Style), With<BoxSelectionRect>>,
+) {
+    let (min, max) = match box_selection.rect().filter(|_| settings.show_rect) {
+        Some(rect) => rect,
+        None => {
+            for (entity, _) in rect_query.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+            return;
+        }
+    };
+    let position = UiRect {
+        left: Val::Px(min.x),
+        bottom: Val::Px(min.y),
+        ..Default::default()
+    };
+    let size = Size::new(Val::Px(max.x - min.x), Val::Px(max.y - min.y));
+    match rect_query.get_single_mut() {
+        Ok((_, mut style)) => {
+            style.position = position;
+            style.size = size;
+        }
+        Err(_) => {
+            commands
+                .spawn_bundle(NodeBundle {
+                    style: Style {
+                        position_type: PositionType::Absolute,
+                        position,
+                        size,
+                        ..Default::default()
+                    },
+                    color: settings.rect_color.into(),
+                    focus_policy: bevy::ui::FocusPolicy::Pass,
+                    ..Default::default()
+                })
+                .insert(BoxSelectionRect);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::{PickableMesh, PickingEvent};
+use bevy::prelude::*;
+
+/// Marker component for entities that receive [BubbledPickingEvent]s from their descendants
+/// without being pickable themselves. Ancestors with a [PickableMesh] are always listeners.
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component)]
+pub struct PickingListener;
+
+/// Marker component that stops [PickingEvent]s from bubbling any further up the hierarchy. The
+/// entity with this component still receives the events of its descendants.
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component)]
+pub struct StopPropagation;
+
+/// A [PickingEvent] that targets a descendant of the `listener` entity. This makes it possible to
+/// handle events of child meshes on the parent entity that holds the game logic.
+#[derive(Debug, Clone)]
+pub struct BubbledPickingEvent {
+    /// The entity the event originally targeted.
+    pub target: Entity,
+    /// The ancestor of `target` this event was delivered to.
+    pub listener: Entity,
+    pub event: PickingEvent,
+}
+
+/// Sends a [BubbledPickingEvent] to each listening ancestor of the target of every
+/// [PickingEvent], walking up the [Parent] chain until it reaches the root or an entity with
+/// [StopPropagation].
+#[allow(clippy::type_complexity)]
+pub fn bubble_picking_events(
+    mut picking_events: EventReader<PickingEvent>,
+    mut bubbled_events: EventWriter<BubbledPickingEvent>,
+    parent_query: Query<&Parent>,
+    listener_query: Query<(), Or<(With<PickableMesh>, With<PickingListener>)>>,
+    stop_query: Query<(), With<StopPropagation>>,
+) {
+    for event in picking_events.iter() {
+        let target = event.target();
+        let mut entity = target;
+        while !stop_query.contains(entity) {
+            entity = match parent_query.get(entity) {
+                Ok(parent) => parent.get(),
+                Err(_) => break,
+            };
+            if listener_query.contains(entity) {
+                bubbled_events.send(BubbledPickingEvent {
+                    target,
+                    listener: entity,
+                    event: event.clone(),
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{PausedForBlockers, PickingCamera};
+use bevy::{
+    prelude::*,
+    render::camera::RenderTarget,
+    utils::{HashMap, HashSet},
+    window::WindowId,
+};
+
+/// The cursor icon to show while this entity is the topmost entity under a pick source, added by
+/// the [HoverCursorIconPlugin](crate::HoverCursorIconPlugin). For example, a grab hand for
+/// entities that can be dragged.
+///
+/// [CursorIcon] doesn't implement [Reflect], so the icon itself isn't saved in scenes: a
+/// [HoverCursorIcon] loaded from a scene shows [CursorIcon::Default] until the icon is set again.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct HoverCursorIcon(#[reflect(ignore)] pub CursorIcon);
+
+impl Default for HoverCursorIcon {
+    fn default() -> Self {
+        Self(CursorIcon::Default)
+    }
+}
+
+/// Sets the cursor icon of every window to the [HoverCursorIcon] of the topmost entity hit by a
+/// [PickingCamera] rendering to that window. Windows where no entity with a [HoverCursorIcon] is
+/// hovered are set back to [CursorIcon::Default], but only if this system changed their icon
+/// before. Nothing is hovered while picking is paused by UI.
+pub fn update_hover_cursor_icon(
+    mut changed_windows: Local<HashSet<WindowId>>,
+    paused: Option<Res<PausedForBlockers>>,
+    mut windows: ResMut<Windows>,
+    pick_source_query: Query<(&PickingCamera, &Camera)>,
+    icon_query: Query<&HoverCursorIcon>,
+) {
+    let paused = paused.map_or(false, |paused| paused.is_paused());
+    let mut icons = HashMap::default();
+    for (pick_source, camera) in pick_source_query.iter() {
+        let window = match camera.target {
+            RenderTarget::Window(window) => window,
+            _ => continue,
+        };
+        let icon = pick_source
+            .intersections()
+            .first()
+            .and_then(|(entity, _)| icon_query.get(*entity).ok())
+            .filter(|_| !paused);
+        if let Some(icon) = icon {
+            icons.entry(window).or_insert(icon.0);
+        }
+    }
+
+    for window in windows.iter_mut() {
+        let icon = match icons.get(&window.id()) {
+            Some(icon) => {
+                changed_windows.insert(window.id());
+                *icon
+            }
+            None if changed_windows.remove(&window.id()) => CursorIcon::Default,
+            None => continue,
+        };
+        if window.cursor_icon() != icon {
+            window.set_cursor_icon(icon);
+        }
+    }
+}
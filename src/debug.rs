@@ -0,0 +1,320 @@
+use crate::{NotPickable, PickingCamera};
+use bevy::{prelude::*, utils::HashSet};
+
+/// Settings for the debug cursor drawn by the
+/// [DebugCursorPickingPlugin](crate::DebugCursorPickingPlugin).
+#[derive(Clone, Debug, Resource)]
+pub struct DebugCursorSettings {
+    /// Show the debug visuals. When disabled, all debug entities are despawned; they are spawned
+    /// again when re-enabled.
+    pub enabled: bool,
+    /// Scale the cursor with its distance from the pick source, so it stays roughly the same size
+    /// on screen. When `false`, the cursor is always [`base_size`](Self::base_size) world units.
+    pub scale_with_distance: bool,
+    /// Size of the cursor, in world units, when it isn't scaled with distance.
+    pub base_size: f32,
+    /// Size of the cursor per world unit of distance from the pick source.
+    pub distance_factor: f32,
+    /// Smallest size the cursor is scaled down to.
+    pub min_size: f32,
+    /// Largest size the cursor is scaled up to.
+    pub max_size: f32,
+    /// Draw each pick source's ray, up to its nearest intersection. Rays cast through the cursor
+    /// start at the camera, so they are only visible from another camera.
+    pub show_rays: bool,
+    /// How far rays that don't hit anything are drawn.
+    pub ray_length: f32,
+    /// Show a text overlay with the nearest intersection of every pick source. Requires
+    /// [`text_font`](Self::text_font) to be set.
+    pub show_text: bool,
+    /// The font used for the text overlay.
+    pub text_font: Handle<Font>,
+}
+
+impl Default for DebugCursorSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            scale_with_distance: true,
+            base_size: 0.16,
+            distance_factor: 0.02,
+            min_size: 0.01,
+            max_size: 100.0,
+            show_rays: false,
+            ray_length: 100.0,
+            show_text: false,
+            text_font: Handle::default(),
+        }
+    }
+}
+
+impl DebugCursorSettings {
+    /// The size of a cursor placed `distance` world units away from its pick source.
+    pub fn size_at(&self, distance: f32) -> f32 {
+        if self.scale_with_distance {
+            (distance * self.distance_factor).clamp(self.min_size, self.max_size)
+        } else {
+            self.base_size
+        }
+    }
+}
+
+/// Marks the debug cursor that shows the nearest intersection of the [PickingCamera] on `source`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DebugCursor {
+    pub source: Entity,
+}
+
+/// Marks the debug visualization of the ray cast by the [PickingCamera] on `source`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DebugRay {
+    pub source: Entity,
+}
+
+/// Marks the debug text overlay.
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct DebugText;
+
+/// Meshes shared by all debug cursors.
+#[derive(Clone, Debug, Resource)]
+pub struct DebugCursorAssets {
+    ball: Handle<Mesh>,
+    tail: Handle<Mesh>,
+    ray: Handle<Mesh>,
+}
+
+impl FromWorld for DebugCursorAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mut meshes = world.resource_mut::<Assets<Mesh>>();
+        let ball = meshes.add(Mesh::from(shape::Icosphere {
+            radius: 0.5,
+            subdivisions: 2,
+        }));
+        let tail = meshes.add(Mesh::from(shape::Box::new(0.1, 1.0, 0.1)));
+        // A unit length beam starting at the origin, stretched along Y to the length of the ray.
+        let ray = meshes.add(Mesh::from(shape::Box {
+            min_x: -0.005,
+            max_x: 0.005,
+            min_y: 0.0,
+            max_y: 1.0,
+            min_z: -0.005,
+            max_z: 0.005,
+        }));
+        DebugCursorAssets { ball, tail, ray }
+    }
+}
+
+/// Spawns a debug cursor for every [PickingCamera], each in its own color, and moves it to the
+/// source's nearest intersection, oriented along the surface normal. Cursors are hidden while
+/// nothing is hit, and despawned along with their source.
+pub fn update_debug_cursor(
+    mut commands: Commands,
+    settings: Res<DebugCursorSettings>,
+    assets: Res<DebugCursorAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    pick_source_query: Query<(Entity, &PickingCamera)>,
+    mut cursor_query: Query<(Entity, &DebugCursor, &mut Transform, &mut Visibility)>,
+) {
+    let mut sources_with_cursor = HashSet::default();
+    for (entity, cursor, mut transform, mut visibility) in cursor_query.iter_mut() {
+        let pick_source = match pick_source_query.get(cursor.source) {
+            Ok((_, pick_source)) => pick_source,
+            Err(_) => {
+                commands.entity(entity).despawn_recursive();
+                continue;
+            }
+        };
+        sources_with_cursor.insert(cursor.source);
+        match pick_source.intersections().first() {
+            Some((_, intersection)) => {
+                *transform = Transform::from_translation(intersection.position())
+                    .with_rotation(Quat::from_rotation_arc(Vec3::Y, intersection.normal()))
+                    .with_scale(Vec3::splat(settings.size_at(intersection.distance())));
+                visibility.is_visible = true;
+            }
+            None => visibility.is_visible = false,
+        }
+    }
+
+    for (source, _) in pick_source_query.iter() {
+        if sources_with_cursor.contains(&source) {
+            continue;
+        }
+        let material = materials.add(StandardMaterial {
+            base_color: source_color(source),
+            unlit: true,
+            ..Default::default()
+        });
+        commands
+            .spawn_bundle(PbrBundle {
+                mesh: assets.ball.clone(),
+                material: material.clone(),
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(DebugCursor { source })
+            .insert(NotPickable)
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(PbrBundle {
+                        mesh: assets.tail.clone(),
+                        material,
+                        transform: Transform::from_xyz(0.0, 1.0, 0.0),
+                        ..Default::default()
+                    })
+                    .insert(NotPickable);
+            });
+    }
+}
+
+/// Draws a beam along the ray of every [PickingCamera] when [DebugCursorSettings::show_rays] is
+/// enabled, each source in its own color.
+pub fn update_debug_rays(
+    mut commands: Commands,
+    settings: Res<DebugCursorSettings>,
+    assets: Res<DebugCursorAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    pick_source_query: Query<(Entity, &PickingCamera)>,
+    mut ray_query: Query<(Entity, &DebugRay, &mut Transform, &mut Visibility)>,
+) {
+    let mut sources_with_ray = HashSet::default();
+    for (entity, debug_ray, mut transform, mut visibility) in ray_query.iter_mut() {
+        let pick_source = match pick_source_query.get(debug_ray.source) {
+            Ok((_, pick_source)) => pick_source,
+            Err(_) => {
+                commands.entity(entity).despawn();
+                continue;
+            }
+        };
+        sources_with_ray.insert(debug_ray.source);
+        let ray = if settings.show_rays {
+            pick_source.ray()
+        } else {
+            None
+        };
+        match ray {
+            Some(ray) => {
+                let length = pick_source
+                    .intersections()
+                    .first()
+                    .map_or(settings.ray_length, |(_, intersection)| {
+                        intersection.distance()
+                    });
+                *transform = Transform::from_translation(ray.origin())
+                    .with_rotation(Quat::from_rotation_arc(Vec3::Y, ray.direction()))
+                    .with_scale(Vec3::new(1.0, length, 1.0));
+                visibility.is_visible = true;
+            }
+            None => visibility.is_visible = false,
+        }
+    }
+
+    if !settings.show_rays {
+        return;
+    }
+    for (source, _) in pick_source_query.iter() {
+        if sources_with_ray.contains(&source) {
+            continue;
+        }
+        commands
+            .spawn_bundle(PbrBundle {
+                mesh: assets.ray.clone(),
+                material: materials.add(StandardMaterial {
+                    base_color: source_color(source),
+                    unlit: true,
+                    ..Default::default()
+                }),
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(DebugRay { source })
+            .insert(NotPickable);
+    }
+}
+
+/// Shows the nearest intersection of every [PickingCamera] in a text overlay when
+/// [DebugCursorSettings::show_text] is enabled, and despawns the overlay when it is disabled.
+pub fn update_debug_text(
+    mut commands: Commands,
+    settings: Res<DebugCursorSettings>,
+    pick_source_query: Query<(Entity, &PickingCamera)>,
+    mut text_query: Query<(Entity, &mut Text), With<DebugText>>,
+) {
+    if !settings.show_text {
+        for (entity, _) in text_query.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let value = pick_source_query
+        .iter()
+        .map(
+            |(source, pick_source)| match pick_source.intersections().first() {
+                Some((entity, intersection)) => format!(
+                    "{:?}: {:?} at {} normal {} distance {:.2} ({} hits)",
+                    source,
+                    entity,
+                    intersection.position(),
+                    intersection.normal(),
+                    intersection.distance(),
+                    pick_source.intersections().len(),
+                ),
+                None => format!("{:?}: -", source),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    match text_query.get_single_mut() {
+        Ok((_, mut text)) => {
+            if text.sections[0].value != value {
+                text.sections[0].value = value;
+            }
+        }
+        Err(_) => {
+            commands
+                .spawn_bundle(
+                    TextBundle::from_section(
+                        value,
+                        TextStyle {
+                            font: settings.text_font.clone(),
+                            font_size: 16.0,
+                            color: Color::WHITE,
+                        },
+                    )
+                    .with_style(Style {
+                        position_type: PositionType::Absolute,
+                        position: UiRect {
+                            top: Val::Px(5.0),
+                            left: Val::Px(5.0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    }),
+                )
+                .insert(DebugText);
+        }
+    }
+}
+
+/// Despawns every debug cursor, ray, and text overlay when [DebugCursorSettings::enabled] is
+/// turned off.
+#[allow(clippy::type_complexity)]
+pub fn despawn_debug_visuals(
+    mut commands: Commands,
+    settings: Res<DebugCursorSettings>,
+    debug_query: Query<Entity, Or<(With<DebugCursor>, With<DebugRay>, With<DebugText>)>>,
+) {
+    if !settings.is_changed() || settings.enabled {
+        return;
+    }
+    for entity in debug_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// A distinct color for the debug visuals of each pick source.
+fn source_color(source: Entity) -> Color {
+    Color::hsl((source.id() as f32 * 137.5) % 360.0, 1.0, 0.5)
+}
@@ -0,0 +1,105 @@
+use crate::{PickableMesh, PickingCamera, PickingSystem, UpdatePicksCriteria};
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
+    prelude::*,
+    utils::{Duration, Instant},
+};
+
+/// Adds picking diagnostics, which can be logged with
+/// [LogDiagnosticsPlugin](bevy::diagnostic::LogDiagnosticsPlugin) like any other diagnostic.
+#[derive(Default)]
+pub struct PickingDiagnosticsPlugin;
+
+impl PickingDiagnosticsPlugin {
+    /// Time in milliseconds spent casting the rays of every [PickingCamera] against the
+    /// [PickableMesh]es. Custom backends and filters aren't included. Frames where no rays are
+    /// cast, because of the [PickingSettings](crate::PickingSettings), aren't measured.
+    pub const RAYCAST_DURATION: DiagnosticId =
+        DiagnosticId::from_u128(304081561740532882651483232690574398505);
+    /// Number of [PickableMesh]es that can be hit.
+    pub const PICKABLE_MESHES: DiagnosticId =
+        DiagnosticId::from_u128(220337719326727218713815565245076573127);
+    /// Number of [PickingCamera]s casting rays.
+    pub const PICK_SOURCES: DiagnosticId =
+        DiagnosticId::from_u128(264005903258689646304989786837156931295);
+    /// Number of intersections found by all [PickingCamera]s, after filtering.
+    pub const INTERSECTIONS: DiagnosticId =
+        DiagnosticId::from_u128(3455473338628412601976029301235800097);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(
+            Self::RAYCAST_DURATION,
+            "picking_raycast_ms",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(Self::PICKABLE_MESHES, "picking_meshes", 20));
+        diagnostics.add(Diagnostic::new(Self::PICK_SOURCES, "picking_sources", 20));
+        diagnostics.add(Diagnostic::new(
+            Self::INTERSECTIONS,
+            "picking_intersections",
+            20,
+        ));
+    }
+
+    fn start_raycast_timer(mut timer: ResMut<RaycastTimer>) {
+        timer.start = Some(Instant::now());
+    }
+
+    fn stop_raycast_timer(mut timer: ResMut<RaycastTimer>) {
+        if let Some(start) = timer.start.take() {
+            timer.duration = Some(start.elapsed());
+        }
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        mut timer: ResMut<RaycastTimer>,
+        mesh_query: Query<(), With<PickableMesh>>,
+        pick_source_query: Query<&PickingCamera>,
+    ) {
+        if let Some(duration) = timer.duration.take() {
+            let duration = duration.as_secs_f64() * 1000.0;
+            diagnostics.add_measurement(Self::RAYCAST_DURATION, duration);
+        }
+        diagnostics.add_measurement(Self::PICKABLE_MESHES, mesh_query.iter().count() as f64);
+        diagnostics.add_measurement(Self::PICK_SOURCES, pick_source_query.iter().count() as f64);
+        let intersections = pick_source_query
+            .iter()
+            .map(|pick_source| pick_source.intersections().len())
+            .sum::<usize>();
+        diagnostics.add_measurement(Self::INTERSECTIONS, intersections as f64);
+    }
+}
+
+impl Plugin for PickingDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RaycastTimer>()
+            .add_startup_system(Self::setup_system)
+            .add_system_set_to_stage(
+                CoreStage::First,
+                SystemSet::new()
+                    .with_run_criteria(UpdatePicksCriteria)
+                    .with_system(
+                        Self::start_raycast_timer
+                            .after(PickingSystem::BuildRays)
+                            .before(PickingSystem::UpdateRaycast),
+                    )
+                    .with_system(
+                        Self::stop_raycast_timer
+                            .after(PickingSystem::UpdateRaycast)
+                            .before(PickingSystem::UpdateIntersections),
+                    ),
+            )
+            .add_system_to_stage(
+                CoreStage::First,
+                Self::diagnostic_system.after(PickingSystem::FilterIntersections),
+            );
+    }
+}
+
+/// When ray casting started this frame, and how long it took once it's done.
+#[derive(Debug, Default, Resource)]
+pub struct RaycastTimer {
+    start: Option<Instant>,
+    duration: Option<Duration>,
+}
@@ -0,0 +1,326 @@
+use crate::{
+    hits::entity_intersection,
+    mouse::{cursor_position, press_cancelled},
+    DragEvent, Hover, PausedForBlockers, PickingCamera, PickingEvent, Primitive3d,
+};
+use bevy::{prelude::*, window::WindowFocused};
+
+/// Resource that configures when pressing on an entity turns into dragging it.
+#[derive(Clone, Debug, Resource)]
+pub struct DragSettings {
+    /// How far, in logical pixels, the pointer must move while pressed before a drag starts. Until
+    /// then, releasing the pointer still counts as a click, so this is also how far the pointer
+    /// can drift during a click or tap. Beyond it, releasing the pointer never sends a
+    /// [PickingEvent::Clicked].
+    pub threshold: f32,
+}
+
+impl Default for DragSettings {
+    fn default() -> Self {
+        Self { threshold: 5.0 }
+    }
+}
+
+impl DragSettings {
+    /// Returns `true` if the pointer moved further than the threshold between being pressed at
+    /// `start` and released at `end`, so the press was a drag rather than a click. Without both
+    /// positions there is no drag to tell apart.
+    pub(crate) fn is_drag(&self, start: Option<Vec2>, end: Option<Vec2>) -> bool {
+        match (start, end) {
+            (Some(start), Some(end)) => start.distance(end) > self.threshold,
+            _ => false,
+        }
+    }
+}
+
+/// Constrains dragging an entity to a plane through the entity's origin, e.g. to move it around on
+/// the ground. While the entity is dragged, [DragEvent::MovedOnPlane] events report where the
+/// pointer's ray puts the entity on the plane. The point on the entity that was pressed on stays
+/// under the pointer, including the distance the pointer moved before the drag started.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct DragPlane {
+    /// The normal of the plane, in world space.
+    pub normal: Vec3,
+    #[reflect(ignore)]
+    grab: Option<Grab>,
+}
+
+impl Default for DragPlane {
+    fn default() -> Self {
+        Self::ground()
+    }
+}
+
+impl DragPlane {
+    pub fn new(normal: Vec3) -> Self {
+        Self {
+            normal: normal.normalize(),
+            grab: None,
+        }
+    }
+
+    /// A horizontal plane, for dragging entities around on the ground.
+    pub fn ground() -> Self {
+        Self::new(Vec3::Y)
+    }
+}
+
+/// Where an entity with a [DragPlane] was grabbed.
+#[derive(Debug, Clone, Copy)]
+struct Grab {
+    /// A point on the plane.
+    origin: Vec3,
+    /// The entity's position relative to the point that was grabbed.
+    offset: Vec3,
+    last: Vec3,
+}
+
+/// Tracks the drag gesture of the mouse or touch pointer.
+#[derive(Debug, Default, Resource)]
+pub struct DragState {
+    press: Option<Press>,
+}
+
+/// A press on a pickable entity that might turn into a drag.
+#[derive(Debug, Clone, Copy)]
+struct Press {
+    entity: Entity,
+    start: Vec2,
+    last: Vec2,
+    dragging: bool,
+    /// The entity under the pointer that the dragged entity would be dropped on.
+    target: Option<Entity>,
+}
+
+impl DragState {
+    /// The entity the pointer is pressed on, whether or not it is being dragged yet.
+    pub fn pressed_entity(&self) -> Option<Entity> {
+        self.press.map(|press| press.entity)
+    }
+
+    /// The entity being dragged, once the pointer has moved past the [DragSettings] threshold.
+    pub fn dragged_entity(&self) -> Option<Entity> {
+        self.press
+            .filter(|press| press.dragging)
+            .map(|press| press.entity)
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.dragged_entity().is_some()
+    }
+
+    /// The entity under the pointer that the dragged entity would be dropped on if released.
+    pub fn drop_target(&self) -> Option<Entity> {
+        self.press
+            .filter(|press| press.dragging)
+            .and_then(|press| press.target)
+    }
+}
+
+/// Sends [DragEvent]s when the pointer is pressed on a pickable entity and moved. The drag keeps
+/// going when the pointer moves off the entity, until the pointer is released.
+///
+/// While dragging, the nearest pickable entity under the pointer other than the dragged entity
+/// itself is the drop target. The dragged entity is skipped so it doesn't hide the entities
+/// underneath it when it follows the pointer.
+///
+/// If the press is cancelled, e.g. because the window lost focus, the drag ends without dropping
+/// the dragged entity.
+#[allow(clippy::too_many_arguments)]
+pub fn mesh_drag_system(
+    paused: Option<Res<PausedForBlockers>>,
+    settings: Res<DragSettings>,
+    mut drag_state: ResMut<DragState>,
+    mouse_button_input: Res<Input<MouseButton>>,
+    touches_input: Res<Touches>,
+    mut window_focused: EventReader<WindowFocused>,
+    mut picking_events: EventWriter<PickingEvent>,
+    pick_source_query: Query<&PickingCamera>,
+    hover_query: Query<&Hover>,
+) {
+    if press_cancelled(&touches_input, &mut window_focused) {
+        if let Some(entity) = drag_state.dragged_entity() {
+            picking_events.send(PickingEvent::Drag(DragEvent::Ended(entity)));
+        }
+        drag_state.press = None;
+        return;
+    }
+    if mouse_button_input.just_released(MouseButton::Left)
+        || touches_input.iter_just_released().next().is_some()
+    {
+        if let Some(entity) = drag_state.dragged_entity() {
+            if let Some(target) = drag_state.drop_target() {
+                picking_events.send(PickingEvent::Drag(DragEvent::Dropped {
+                    dragged: entity,
+                    target,
+                }));
+            }
+            picking_events.send(PickingEvent::Drag(DragEvent::Ended(entity)));
+        }
+        drag_state.press = None;
+        return;
+    }
+
+    let position = cursor_position(&pick_source_query);
+
+    if mouse_button_input.just_pressed(MouseButton::Left)
+        || touches_input.iter_just_pressed().next().is_some()
+    {
+        let paused = paused.map_or(false, |paused| paused.is_paused());
+        drag_state.press = match position {
+            Some(position) if !paused => topmost_hovered_entity(&pick_source_query, &hover_query)
+                .map(|entity| Press {
+                    entity,
+                    start: position,
+                    last: position,
+                    dragging: false,
+                    target: None,
+                }),
+            _ => None,
+        };
+        return;
+    }
+
+    let (mut press, position) = match (drag_state.press, position) {
+        (Some(press), Some(position)) => (press, position),
+        _ => return,
+    };
+    if !press.dragging && press.start.distance(position) > settings.threshold {
+        press.dragging = true;
+        picking_events.send(PickingEvent::Drag(DragEvent::Started(press.entity)));
+    }
+    if press.dragging && position != press.last {
+        picking_events.send(PickingEvent::Drag(DragEvent::Dragged {
+            entity: press.entity,
+            delta: position - press.last,
+            hit: hit_position(&pick_source_query, press.entity),
+        }));
+        press.last = position;
+    }
+    if press.dragging {
+        let dragged = press.entity;
+        let target = drop_target(&pick_source_query, &hover_query, dragged);
+        if target != press.target {
+            if let Some(target) = press.target {
+                picking_events.send(PickingEvent::Drag(DragEvent::Left { dragged, target }));
+            }
+            if let Some(target) = target {
+                picking_events.send(PickingEvent::Drag(DragEvent::Entered { dragged, target }));
+            }
+            press.target = target;
+        }
+        if let Some(target) = target {
+            picking_events.send(PickingEvent::Drag(DragEvent::Over { dragged, target }));
+        }
+    }
+    drag_state.press = Some(press);
+}
+
+/// Sends [DragEvent::MovedOnPlane] events for the dragged entity if it has a [DragPlane]. Nothing
+/// is sent while the pointer's ray is parallel to the plane or points away from it.
+pub fn drag_plane_system(
+    drag_state: Res<DragState>,
+    mut picking_events: EventWriter<PickingEvent>,
+    pick_source_query: Query<&PickingCamera>,
+    mut plane_query: Query<(Entity, &mut DragPlane, &GlobalTransform)>,
+) {
+    let pressed = drag_state.pressed_entity();
+    let dragging = drag_state.is_dragging();
+    let ray = pick_source_query
+        .iter()
+        .find_map(|pick_source| pick_source.ray());
+    for (entity, mut plane, transform) in plane_query.iter_mut() {
+        if Some(entity) != pressed {
+            if plane.grab.is_some() {
+                plane.grab = None;
+            }
+            continue;
+        }
+        let ray = match ray {
+            Some(ray) => ray,
+            None => continue,
+        };
+        let normal = plane.normal;
+        let intersect = |point: Vec3| {
+            ray.intersects_primitive(Primitive3d::Plane { point, normal })
+                .map(|intersection| intersection.position())
+        };
+        // Grab the entity where it was pressed, not where the pointer is once the drag starts.
+        let mut grab = match plane.grab {
+            Some(grab) => grab,
+            None => {
+                let origin = transform.translation();
+                match intersect(origin) {
+                    Some(hit) => Grab {
+                        origin,
+                        offset: origin - hit,
+                        last: origin,
+                    },
+                    None => continue,
+                }
+            }
+        };
+        if let Some(hit) = intersect(grab.origin).filter(|_| dragging) {
+            let position = hit + grab.offset;
+            if position != grab.last {
+                picking_events.send(PickingEvent::Drag(DragEvent::MovedOnPlane {
+                    entity,
+                    position,
+                    delta: position - grab.last,
+                }));
+                grab.last = position;
+            }
+        }
+        plane.grab = Some(grab);
+    }
+}
+
+/// The nearest pickable entity under the pointer, ignoring the dragged entity.
+fn drop_target(
+    pick_source_query: &Query<&PickingCamera>,
+    hover_query: &Query<&Hover>,
+    dragged: Entity,
+) -> Option<Entity> {
+    pick_source_query.iter().find_map(|pick_source| {
+        pick_source
+            .intersect_list()?
+            .iter()
+            .map(|(entity, _)| *entity)
+            .find(|entity| *entity != dragged && hover_query.get(*entity).is_ok())
+    })
+}
+
+/// The nearest hovered entity under the pointer.
+pub(crate) fn topmost_hovered_entity(
+    pick_source_query: &Query<&PickingCamera>,
+    hover_query: &Query<&Hover>,
+) -> Option<Entity> {
+    topmost_entity(pick_source_query, |entity| {
+        hover_query
+            .get(entity)
+            .map_or(false, |hover| hover.hovered())
+    })
+}
+
+/// The nearest entity under the pointer of any pick source that matches `filter`.
+pub(crate) fn topmost_entity(
+    pick_source_query: &Query<&PickingCamera>,
+    filter: impl Fn(Entity) -> bool,
+) -> Option<Entity> {
+    pick_source_query
+        .iter()
+        .filter_map(|pick_source| {
+            pick_source
+                .intersect_list()?
+                .iter()
+                .find(|(entity, _)| filter(*entity))
+        })
+        .min_by(|(_, a), (_, b)| a.distance().total_cmp(&b.distance()))
+        .map(|(entity, _)| *entity)
+}
+
+/// The world position where the pointer intersects the entity, if it is under the pointer.
+fn hit_position(pick_source_query: &Query<&PickingCamera>, entity: Entity) -> Option<Vec3> {
+    entity_intersection(pick_source_query, entity).map(|intersection| intersection.position())
+}
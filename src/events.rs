@@ -1,34 +1,199 @@
-use crate::{Hover, PickableMesh, Selection};
-use bevy::prelude::*;
+use crate::{
+    drag::topmost_hovered_entity,
+    hits::entity_intersection,
+    mouse::{cursor_position, press_cancelled},
+    DragSettings, DragState, Hover, IntersectionData, PickableMesh, PickingCamera, Selection,
+};
+use bevy::{prelude::*, utils::HashSet, window::WindowFocused};
 
 /// An event that triggers when the selection state of a [Selection] enabled [PickableMesh] changes.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum SelectionEvent {
     JustSelected(Entity),
     JustDeselected(Entity),
 }
 
 /// An event that triggers when the hover state of a [Hover] enabled [PickableMesh] changes.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum HoverEvent {
     JustEntered(Entity),
     JustLeft(Entity),
 }
 
+/// An event that triggers when a [PickableMesh] is dragged. See [DragSettings](crate::DragSettings)
+/// for when a press turns into a drag.
+#[derive(Debug, Clone)]
+pub enum DragEvent {
+    Started(Entity),
+    Dragged {
+        entity: Entity,
+        /// How far the pointer moved since the last drag event, in logical pixels.
+        delta: Vec2,
+        /// Where the pointer intersects the dragged entity in world space, if it is still under
+        /// the pointer.
+        hit: Option<Vec3>,
+    },
+    /// The dragged entity, which has a [DragPlane](crate::DragPlane), was moved along its plane.
+    /// The point where it was grabbed stays under the pointer.
+    MovedOnPlane {
+        entity: Entity,
+        /// The new world position of the entity.
+        position: Vec3,
+        /// How far the entity moved since the last event, in world space.
+        delta: Vec3,
+    },
+    /// The dragged entity was moved over a pickable entity.
+    Entered {
+        dragged: Entity,
+        target: Entity,
+    },
+    /// The dragged entity is over a pickable entity. Sent every frame until it leaves the target.
+    Over {
+        dragged: Entity,
+        target: Entity,
+    },
+    /// The dragged entity was moved off a pickable entity.
+    Left {
+        dragged: Entity,
+        target: Entity,
+    },
+    /// The dragged entity was released over a pickable entity. Sent before [DragEvent::Ended].
+    Dropped {
+        dragged: Entity,
+        target: Entity,
+    },
+    Ended(Entity),
+}
+
 /// An event that wraps selection and hover events
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum PickingEvent {
     Selection(SelectionEvent),
     Hover(HoverEvent),
-    Clicked(Entity),
+    Drag(DragEvent),
+    /// Sent when the pointer is pressed and released over the same entity. Pressing on one entity
+    /// and releasing over another clicks neither, and neither does dragging an entity.
+    Clicked {
+        entity: Entity,
+        /// Where the pointer intersected the entity when it was released.
+        hit: Option<IntersectionData>,
+    },
+    /// Sent when an entity is clicked twice in quick succession. Both clicks also send a
+    /// [PickingEvent::Clicked].
+    DoubleClicked {
+        entity: Entity,
+        /// Where the pointer intersected the entity on the second click.
+        hit: Option<IntersectionData>,
+    },
+    /// Sent once when the pointer has rested on an entity for the [TimedEventSettings] hover
+    /// timeout, e.g. to show a tooltip.
+    HoverTimeout(Entity),
+    /// Sent when the pointer has been held on an entity for the [TimedEventSettings] long press
+    /// duration. Releasing the pointer afterwards doesn't click the entity.
+    LongPressed {
+        entity: Entity,
+        /// Where the pointer intersects the entity.
+        hit: Option<IntersectionData>,
+    },
+}
+
+impl PickingEvent {
+    /// The entity this event is about. For drag and drop events between two entities, this is the
+    /// entity being dragged over or dropped on.
+    pub fn target(&self) -> Entity {
+        match self {
+            PickingEvent::Selection(SelectionEvent::JustSelected(entity))
+            | PickingEvent::Selection(SelectionEvent::JustDeselected(entity))
+            | PickingEvent::Hover(HoverEvent::JustEntered(entity))
+            | PickingEvent::Hover(HoverEvent::JustLeft(entity))
+            | PickingEvent::Drag(DragEvent::Started(entity))
+            | PickingEvent::Drag(DragEvent::Dragged { entity, .. })
+            | PickingEvent::Drag(DragEvent::MovedOnPlane { entity, .. })
+            | PickingEvent::Drag(DragEvent::Ended(entity))
+            | PickingEvent::Clicked { entity, .. }
+            | PickingEvent::DoubleClicked { entity, .. }
+            | PickingEvent::HoverTimeout(entity)
+            | PickingEvent::LongPressed { entity, .. } => *entity,
+            PickingEvent::Drag(DragEvent::Entered { target, .. })
+            | PickingEvent::Drag(DragEvent::Over { target, .. })
+            | PickingEvent::Drag(DragEvent::Left { target, .. })
+            | PickingEvent::Drag(DragEvent::Dropped { target, .. }) => *target,
+        }
+    }
 }
 
-/// Looks for changes in selection or hover state, and sends the appropriate events
-#[allow(clippy::type_complexity)]
+/// Resource that configures how [PickingEvent::DoubleClicked] events are detected.
+#[derive(Clone, Debug, Resource)]
+pub struct DoubleClickSettings {
+    /// The maximum time in seconds between two clicks for them to count as a double click.
+    pub max_delay: f64,
+    /// The maximum distance in logical pixels the cursor may move between two clicks for them to
+    /// count as a double click.
+    pub max_distance: f32,
+}
+
+impl Default for DoubleClickSettings {
+    fn default() -> Self {
+        Self {
+            max_delay: 0.5,
+            max_distance: 5.0,
+        }
+    }
+}
+
+/// Resource that configures when [PickingEvent::HoverTimeout] and [PickingEvent::LongPressed]
+/// events are sent.
+#[derive(Clone, Debug, Resource)]
+pub struct TimedEventSettings {
+    /// How long in seconds the pointer must rest on an entity before it is sent a
+    /// [PickingEvent::HoverTimeout].
+    pub hover_timeout: f64,
+    /// How far in logical pixels the pointer may move while resting on an entity.
+    pub hover_tolerance: f32,
+    /// How long in seconds the pointer must be held on an entity for a [PickingEvent::LongPressed].
+    pub long_press: f64,
+}
+
+impl Default for TimedEventSettings {
+    fn default() -> Self {
+        Self {
+            hover_timeout: 0.5,
+            hover_tolerance: 5.0,
+            long_press: 0.6,
+        }
+    }
+}
+
+/// The last click, used to detect double clicks.
+#[derive(Debug, Clone, Copy)]
+pub struct LastClick {
+    entity: Entity,
+    time: f64,
+    position: Option<Vec2>,
+}
+
+/// Looks for changes in selection or hover state, and sends the appropriate events.
+///
+/// Within a frame, [HoverEvent::JustLeft] events are always sent before [HoverEvent::JustEntered]
+/// events, so moving directly from one entity to another reads as leaving the first, then entering
+/// the second. Entities that are despawned while hovered also send a [HoverEvent::JustLeft].
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn mesh_events_system(
+    mut hovered_entities: Local<HashSet<Entity>>,
+    mut pressed_entities: Local<Vec<Entity>>,
+    mut last_click: Local<Option<LastClick>>,
+    mut press_time: Local<f64>,
+    mut press_position: Local<Option<Vec2>>,
+    time: Res<Time>,
+    double_click_settings: Res<DoubleClickSettings>,
+    timed_event_settings: Res<TimedEventSettings>,
+    drag_settings: Res<DragSettings>,
+    drag_state: Res<DragState>,
     mouse_button_input: Res<Input<MouseButton>>,
     touches_input: Res<Touches>,
+    mut window_focused: EventReader<WindowFocused>,
     mut picking_events: EventWriter<PickingEvent>,
+    pick_source_query: Query<&PickingCamera>,
     hover_query: Query<
         (Entity, &Hover, ChangeTrackers<Hover>),
         (Changed<Hover>, With<PickableMesh>),
@@ -39,16 +204,30 @@ pub fn mesh_events_system(
     >,
     click_query: Query<(Entity, &Hover)>,
 ) {
+    let despawned: Vec<Entity> = hovered_entities
+        .iter()
+        .filter(|entity| click_query.get(**entity).is_err())
+        .copied()
+        .collect();
+    for entity in despawned {
+        hovered_entities.remove(&entity);
+        picking_events.send(PickingEvent::Hover(HoverEvent::JustLeft(entity)));
+    }
+    let mut entered = Vec::new();
     for (entity, hover, hover_change) in hover_query.iter() {
         if hover_change.is_added() {
             continue; // Avoid a false change detection when a component is added.
         }
         if hover.hovered() {
-            picking_events.send(PickingEvent::Hover(HoverEvent::JustEntered(entity)));
-        } else {
+            entered.push(entity);
+        } else if hovered_entities.remove(&entity) {
             picking_events.send(PickingEvent::Hover(HoverEvent::JustLeft(entity)));
         }
     }
+    for entity in entered {
+        hovered_entities.insert(entity);
+        picking_events.send(PickingEvent::Hover(HoverEvent::JustEntered(entity)));
+    }
     for (entity, selection, selection_change) in selection_query.iter() {
         if selection_change.is_added() {
             continue; // Avoid a false change detection when a component is added.
@@ -66,11 +245,124 @@ pub fn mesh_events_system(
     if mouse_button_input.just_pressed(MouseButton::Left)
         || touches_input.iter_just_pressed().next().is_some()
     {
+        *pressed_entities = click_query
+            .iter()
+            .filter(|(_, hover)| hover.hovered())
+            .map(|(entity, _)| entity)
+            .collect();
+        *press_time = time.seconds_since_startup();
+        *press_position = cursor_position(&pick_source_query);
+    }
+    if drag_state.is_dragging() || press_cancelled(&touches_input, &mut window_focused) {
+        // The press turned into a drag or was cancelled, it can no longer become a click.
+        pressed_entities.clear();
+    }
+    if !pressed_entities.is_empty()
+        && time.seconds_since_startup() - *press_time >= timed_event_settings.long_press
+    {
+        for (entity, hover) in click_query.iter() {
+            if hover.hovered() && pressed_entities.contains(&entity) {
+                picking_events.send(PickingEvent::LongPressed {
+                    entity,
+                    hit: entity_intersection(&pick_source_query, entity),
+                });
+            }
+        }
+        // A long press replaces the click.
+        pressed_entities.clear();
+    }
+    if mouse_button_input.just_released(MouseButton::Left)
+        || touches_input.iter_just_released().next().is_some()
+    {
+        let now = time.seconds_since_startup();
+        let position = cursor_position(&pick_source_query);
+        // The drag only starts after this system has run, so a pointer that moves past the drag
+        // threshold and is released within the same frame must not click either.
+        if drag_settings.is_drag(*press_position, position) {
+            pressed_entities.clear();
+        }
+        // Entities despawned since the press are no longer in the query, so they are not clicked.
         for (entity, hover) in click_query.iter() {
-            if hover.hovered() {
-                picking_events.send(PickingEvent::Clicked(entity));
+            if hover.hovered() && pressed_entities.contains(&entity) {
+                let hit = entity_intersection(&pick_source_query, entity);
+                picking_events.send(PickingEvent::Clicked {
+                    entity,
+                    hit: hit.clone(),
+                });
+                let is_double_click = last_click.map_or(false, |last| {
+                    last.entity == entity
+                        && now - last.time <= double_click_settings.max_delay
+                        && match (last.position, position) {
+                            (Some(last), Some(current)) => {
+                                last.distance(current) <= double_click_settings.max_distance
+                            }
+                            _ => true,
+                        }
+                });
+                if is_double_click {
+                    picking_events.send(PickingEvent::DoubleClicked { entity, hit });
+                    // A third click starts a new double click instead of completing another one.
+                    *last_click = None;
+                } else {
+                    *last_click = Some(LastClick {
+                        entity,
+                        time: now,
+                        position,
+                    });
+                }
             }
         }
+        pressed_entities.clear();
+    }
+}
+
+/// Where and since when the pointer has been resting on an entity.
+#[derive(Debug, Clone, Copy)]
+pub struct HoverRest {
+    entity: Entity,
+    since: f64,
+    position: Option<Vec2>,
+    sent: bool,
+}
+
+/// Sends a [PickingEvent::HoverTimeout] when the pointer rests on the same entity for the
+/// [TimedEventSettings] hover timeout without moving more than the hover tolerance.
+pub fn hover_timeout_system(
+    mut rest: Local<Option<HoverRest>>,
+    time: Res<Time>,
+    settings: Res<TimedEventSettings>,
+    mut picking_events: EventWriter<PickingEvent>,
+    pick_source_query: Query<&PickingCamera>,
+    hover_query: Query<&Hover>,
+) {
+    let now = time.seconds_since_startup();
+    let position = cursor_position(&pick_source_query);
+    let entity = match topmost_hovered_entity(&pick_source_query, &hover_query) {
+        Some(entity) => entity,
+        None => {
+            *rest = None;
+            return;
+        }
+    };
+    let moved = |rest: &HoverRest| match (rest.position, position) {
+        (Some(start), Some(current)) => start.distance(current) > settings.hover_tolerance,
+        _ => false,
+    };
+    let resting =
+        matches!(&*rest, Some(rest) if rest.entity == entity && (rest.sent || !moved(rest)));
+    if !resting {
+        *rest = Some(HoverRest {
+            entity,
+            since: now,
+            position,
+            sent: false,
+        });
+    }
+    if let Some(rest) = rest.as_mut() {
+        if !rest.sent && now - rest.since >= settings.hover_timeout {
+            picking_events.send(PickingEvent::HoverTimeout(entity));
+            rest.sent = true;
+        }
     }
 }
 
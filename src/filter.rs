@@ -0,0 +1,211 @@
+use crate::{IntersectionData, PickingCamera, PickingPluginsState};
+use bevy::{
+    prelude::*,
+    render::{
+        camera::{CameraProjection, Projection},
+        view::RenderLayers,
+    },
+};
+use std::cmp::Ordering;
+
+/// Limits how far away from a [PickingCamera] entities can be picked. Intersections further than
+/// this distance are discarded before they are used for hovering, selection, or highlighting.
+///
+/// Add this to the entity with the [PickingCamera] component.
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct MaxPickDistance(pub f32);
+
+impl Default for MaxPickDistance {
+    /// No limit.
+    fn default() -> Self {
+        Self(f32::INFINITY)
+    }
+}
+
+/// Limits picking to entities inside the view frustum of the [PickingCamera]'s camera. Rays cast
+/// through the cursor extend forever, so without this, entities behind the far plane or in front
+/// of the near plane can still be hit. Intersections on the near plane are kept.
+///
+/// The near and far planes are those of the camera's [Projection], [PerspectiveProjection], or
+/// [OrthographicProjection]. Perspective depth is infinite, but Bevy still culls meshes beyond
+/// the `far` of a [PerspectiveProjection], so their intersections are discarded too.
+///
+/// Add this to the entity with the [PickingCamera] and [Camera] components.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct ClipToFrustum;
+
+/// Excludes an entity from picking, even if it has a [PickableMesh](crate::PickableMesh) or
+/// [PickableSprite](crate::PickableSprite). Useful when pickable components are added broadly, for
+/// example to every mesh in a scene, and a few entities need to opt out.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct NotPickable;
+
+/// Moves an entity's intersections this many world units towards the pick source when deciding
+/// which entity is in front. Useful to make gizmos or decals win over geometry they coincide with.
+/// Negative values push the entity back. The reported intersection distance is unchanged.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct PickDepthBias(pub f32);
+
+/// Removes intersections with [NotPickable] entities.
+pub fn remove_unpickable_intersections(
+    unpickable_query: Query<(), With<NotPickable>>,
+    mut pick_source_query: Query<&mut PickingCamera>,
+) {
+    if unpickable_query.is_empty() {
+        return;
+    }
+    for mut pick_source in pick_source_query.iter_mut() {
+        if pick_source
+            .intersections()
+            .iter()
+            .any(|(entity, _)| unpickable_query.contains(*entity))
+        {
+            pick_source
+                .intersections_mut()
+                .retain(|(entity, _)| !unpickable_query.contains(*entity));
+        }
+    }
+}
+
+/// Removes intersections with entities that the [PickingCamera]'s camera doesn't render, because
+/// their [RenderLayers] don't overlap. Like the renderer, entities and cameras without
+/// [RenderLayers] are on the default layer.
+pub fn apply_render_layers(
+    layers_query: Query<&RenderLayers>,
+    mut pick_source_query: Query<(Entity, &mut PickingCamera)>,
+) {
+    for (source, mut pick_source) in pick_source_query.iter_mut() {
+        let source_layers = layers_query.get(source).copied().unwrap_or_default();
+        let visible = |entity: Entity| {
+            layers_query
+                .get(entity)
+                .copied()
+                .unwrap_or_default()
+                .intersects(&source_layers)
+        };
+        if pick_source
+            .intersections()
+            .iter()
+            .any(|(entity, _)| !visible(*entity))
+        {
+            pick_source
+                .intersections_mut()
+                .retain(|(entity, _)| visible(*entity));
+        }
+    }
+}
+
+/// Removes intersections beyond the [MaxPickDistance] of their pick source.
+pub fn apply_max_pick_distance(
+    mut pick_source_query: Query<(&mut PickingCamera, &MaxPickDistance)>,
+) {
+    for (mut pick_source, max_distance) in pick_source_query.iter_mut() {
+        if pick_source
+            .intersections()
+            .iter()
+            .any(|(_, intersection)| intersection.distance() > max_distance.0)
+        {
+            pick_source
+                .intersections_mut()
+                .retain(|(_, intersection)| intersection.distance() <= max_distance.0);
+        }
+    }
+}
+
+/// Removes intersections outside the view frustum of pick sources with [ClipToFrustum].
+#[allow(clippy::type_complexity)]
+pub fn apply_frustum_clipping(
+    mut pick_source_query: Query<
+        (
+            &mut PickingCamera,
+            &GlobalTransform,
+            Option<&Projection>,
+            Option<&PerspectiveProjection>,
+            Option<&OrthographicProjection>,
+        ),
+        With<ClipToFrustum>,
+    >,
+) {
+    for (mut pick_source, camera_transform, projection, perspective, orthographic) in
+        pick_source_query.iter_mut()
+    {
+        let (projection_matrix, near, far) = match (projection, perspective, orthographic) {
+            (Some(Projection::Perspective(perspective)), ..) | (None, Some(perspective), _) => (
+                perspective.get_projection_matrix(),
+                perspective.near,
+                perspective.far,
+            ),
+            (Some(Projection::Orthographic(orthographic)), ..)
+            | (None, None, Some(orthographic)) => (
+                orthographic.get_projection_matrix(),
+                orthographic.near,
+                orthographic.far,
+            ),
+            _ => continue,
+        };
+        // Perspective depth is reversed and infinite, so NDC depth can't tell whether a point is
+        // beyond the far plane. Compare the view space depth to the planes instead, allowing for
+        // rounding on the near plane.
+        let near = near - 1e-5 * near.abs().max(1.0);
+        let world_to_view = camera_transform.compute_matrix().inverse();
+        let inside = |position: Vec3| {
+            let view = world_to_view.transform_point3(position);
+            let ndc = projection_matrix.project_point3(view);
+            (near..=far).contains(&-view.z) && ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0
+        };
+        if pick_source
+            .intersections()
+            .iter()
+            .any(|(_, intersection)| !inside(intersection.position()))
+        {
+            pick_source
+                .intersections_mut()
+                .retain(|(_, intersection)| inside(intersection.position()));
+        }
+    }
+}
+
+/// Sorts the intersections of every [PickingCamera] by distance, minus the [PickDepthBias] of the
+/// entity hit. Entities at the same depth are ordered by [Entity], so the topmost entity doesn't
+/// change from frame to frame depending on query iteration order.
+pub fn sort_intersections(
+    bias_query: Query<&PickDepthBias>,
+    mut pick_source_query: Query<&mut PickingCamera>,
+) {
+    let depth = |(entity, intersection): &(Entity, IntersectionData)| {
+        intersection.distance() - bias_query.get(*entity).map_or(0.0, |bias| bias.0)
+    };
+    let order = |a: &(Entity, IntersectionData), b: &(Entity, IntersectionData)| {
+        depth(a)
+            .partial_cmp(&depth(b))
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.0.cmp(&b.0))
+    };
+    for mut pick_source in pick_source_query.iter_mut() {
+        if pick_source
+            .intersections()
+            .windows(2)
+            .any(|pair| order(&pair[0], &pair[1]) == Ordering::Greater)
+        {
+            pick_source.intersections_mut().sort_by(order);
+        }
+    }
+}
+
+/// Clears the intersections of every [PickingCamera] when picking is disabled with
+/// [PickingPluginsState], so downstream systems don't keep acting on stale hits.
+pub fn clear_picks_when_disabled(
+    state: Res<PickingPluginsState>,
+    mut pick_source_query: Query<&mut PickingCamera>,
+) {
+    if !state.is_changed() || state.enable_picking {
+        return;
+    }
+    for mut pick_source in pick_source_query.iter_mut() {
+        pick_source.intersections_mut().clear();
+    }
+}
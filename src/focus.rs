@@ -1,12 +1,13 @@
-use crate::{PausedForBlockers, PickableMesh, PickingCamera};
-use bevy::{prelude::*, ui::FocusPolicy};
+use crate::{mouse::press_cancelled, PausedForBlockers, PickableMesh, PickingCamera};
+use bevy::{prelude::*, ui::FocusPolicy, window::WindowFocused};
 
 /// Tracks the current hover state to be used with change tracking in the events system.
 ///
 /// # Requirements
 ///
 /// An entity with the `Hover` component must also have an [Interaction] component.
-#[derive(Component, Debug, Default, Copy, Clone)]
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component)]
 pub struct Hover {
     hovered: bool,
 }
@@ -19,8 +20,10 @@ impl Hover {
 
 /// Marker component for entities that, whenever their [Interaction] component is anything other
 /// than `None`, will suspend highlighting and selecting [PickableMesh]s. Bevy UI [Node]s have this
-/// behavior by default.
-#[derive(Component)]
+/// behavior by default, unless their [FocusPolicy] is [FocusPolicy::Pass], which is useful for
+/// transparent containers.
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component)]
 pub struct PickingBlocker;
 
 #[allow(clippy::type_complexity)]
@@ -37,24 +40,31 @@ pub fn pause_for_picking_blockers(
             With<PickableMesh>,
         >,
         // UI nodes are picking blockers by default.
-        Query<&Interaction, Or<(With<Node>, With<PickingBlocker>)>>,
+        Query<
+            (&Interaction, Option<&FocusPolicy>, Option<&PickingBlocker>),
+            Or<(With<Node>, With<PickingBlocker>)>,
+        >,
     )>,
 ) {
-    paused.0 = false;
-    for ui_interaction in interactions.p1().iter() {
-        if *ui_interaction != Interaction::None {
-            for (mut interaction, hover, _, _) in &mut interactions.p0().iter_mut() {
-                if *interaction != Interaction::None {
-                    *interaction = Interaction::None;
-                }
-                if let Some(mut hover) = hover {
-                    if hover.hovered {
-                        hover.hovered = false;
-                    }
+    paused.0 = interactions
+        .p1()
+        .iter()
+        .any(|(ui_interaction, focus_policy, blocker)| {
+            // UI nodes that let the pointer through to the nodes below don't block picking either.
+            let passes_through =
+                blocker.is_none() && matches!(focus_policy, Some(FocusPolicy::Pass));
+            *ui_interaction != Interaction::None && !passes_through
+        });
+    if paused.0 {
+        for (mut interaction, hover, _, _) in &mut interactions.p0().iter_mut() {
+            if *interaction != Interaction::None {
+                *interaction = Interaction::None;
+            }
+            if let Some(mut hover) = hover {
+                if hover.hovered {
+                    hover.hovered = false;
                 }
             }
-            paused.0 = true;
-            return;
         }
     }
 }
@@ -64,6 +74,7 @@ pub fn mesh_focus(
     paused: Option<Res<PausedForBlockers>>,
     mouse_button_input: Res<Input<MouseButton>>,
     touches_input: Res<Touches>,
+    mut window_focused: EventReader<WindowFocused>,
     pick_source_query: Query<&PickingCamera>,
     mut interactions: Query<
         (
@@ -81,10 +92,13 @@ pub fn mesh_focus(
         }
     }
 
-    let mut hovered_entity = None;
+    // Entities hovered by any of the pick sources. Each source picks independently, so multiple
+    // cameras or cursors can hover different entities at the same time.
+    let mut hovered_entities = Vec::new();
 
     if mouse_button_input.just_released(MouseButton::Left)
         || touches_input.iter_just_released().next().is_some()
+        || press_cancelled(&touches_input, &mut window_focused)
     {
         for (mut interaction, _, _, _) in &mut interactions.iter_mut() {
             if *interaction == Interaction::Clicked {
@@ -110,7 +124,7 @@ pub fn mesh_focus(
                         *interaction = Interaction::Hovered;
                     }
 
-                    hovered_entity = Some(*topmost_entity);
+                    hovered_entities.push(*topmost_entity);
 
                     match focus_policy.cloned().unwrap_or(FocusPolicy::Block) {
                         FocusPolicy::Block => {
@@ -121,21 +135,16 @@ pub fn mesh_focus(
                 }
             }
         }
+    }
 
-        for (mut interaction, hover, _, entity) in &mut interactions.iter_mut() {
-            if Some(entity) != hovered_entity && *interaction == Interaction::Hovered {
-                *interaction = Interaction::None;
-            }
-            if Some(entity) == hovered_entity {
-                if let Some(mut hover) = hover {
-                    if !hover.hovered {
-                        hover.hovered = true;
-                    }
-                }
-            } else if let Some(mut hover) = hover {
-                if hover.hovered {
-                    hover.hovered = false;
-                }
+    for (mut interaction, hover, _, entity) in &mut interactions.iter_mut() {
+        let hovered = hovered_entities.contains(&entity);
+        if !hovered && *interaction == Interaction::Hovered {
+            *interaction = Interaction::None;
+        }
+        if let Some(mut hover) = hover {
+            if hover.hovered != hovered {
+                hover.hovered = hovered;
             }
         }
     }
@@ -0,0 +1,62 @@
+use crate::{BubbledPickingEvent, PickingEvent, PickingSystem};
+use bevy::prelude::*;
+use std::marker::PhantomData;
+
+/// Implement this for your own event type to have it sent in response to [PickingEvent]s, by adding
+/// a [ForwardPickingEvents] component to the entities you are interested in.
+pub trait FromPickingEvent: Sized + Send + Sync + 'static {
+    /// Creates the event to send when `event` targets `listener`, or bubbles up to it from one of
+    /// its descendants. Return `None` to ignore the event.
+    fn from_picking_event(listener: Entity, event: &PickingEvent) -> Option<Self>;
+}
+
+/// Sends an `E` event whenever a [PickingEvent] targets this entity, or bubbles up to it from one
+/// of its descendants. Requires the [EventForwardingPlugin] for `E` to be added to the app.
+#[derive(Component)]
+pub struct ForwardPickingEvents<E: FromPickingEvent>(PhantomData<fn() -> E>);
+
+impl<E: FromPickingEvent> Default for ForwardPickingEvents<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Converts [PickingEvent]s into `E` events for entities with a [ForwardPickingEvents] component.
+pub fn forward_picking_events<E: FromPickingEvent>(
+    mut picking_events: EventReader<PickingEvent>,
+    mut bubbled_events: EventReader<BubbledPickingEvent>,
+    mut forwarded_events: EventWriter<E>,
+    forward_query: Query<(), With<ForwardPickingEvents<E>>>,
+) {
+    let direct = picking_events.iter().map(|event| (event.target(), event));
+    let bubbled = bubbled_events
+        .iter()
+        .map(|bubbled| (bubbled.listener, &bubbled.event));
+    for (listener, event) in direct.chain(bubbled) {
+        if !forward_query.contains(listener) {
+            continue;
+        }
+        if let Some(forwarded) = E::from_picking_event(listener, event) {
+            forwarded_events.send(forwarded);
+        }
+    }
+}
+
+/// Adds the `E` event, and sends it for entities with a [ForwardPickingEvents] component. Add one
+/// of these plugins for each of your event types.
+pub struct EventForwardingPlugin<E: FromPickingEvent>(PhantomData<fn() -> E>);
+
+impl<E: FromPickingEvent> Default for EventForwardingPlugin<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: FromPickingEvent> Plugin for EventForwardingPlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<E>().add_system_to_stage(
+            CoreStage::First,
+            forward_picking_events::<E>.after(PickingSystem::Bubbling),
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use crate::{NotPickable, PickableMesh, PickingCamera};
+use bevy::{
+    ecs::{
+        entity::{EntityMap, MapEntities, MapEntitiesError},
+        reflect::ReflectMapEntities,
+    },
+    prelude::*,
+    utils::HashSet,
+};
+
+/// Makes every descendant of this entity that has a mesh pickable, including descendants that are
+/// spawned later, e.g. when a scene finishes loading. Hits on those descendants are reported as
+/// hits on this entity, so a whole model is hovered, selected, and dragged as one.
+///
+/// Add this next to a [PickableBundle](crate::PickableBundle), typically on the entity a scene is
+/// spawned with. Descendants with [NotPickable] are left alone, and so are descendants of nested
+/// [PickableHierarchy] entities, which handle their own hierarchy.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct PickableHierarchy;
+
+/// Added by [add_pickable_descendants] to meshes made pickable by a [PickableHierarchy].
+#[derive(Component, Debug, Clone, Copy, Reflect)]
+#[reflect(Component, MapEntities)]
+pub struct PickableDescendant {
+    /// The entity with the [PickableHierarchy] that hits on this entity are reported for.
+    pub root: Entity,
+}
+
+// Only used when loading a scene, where the root is mapped right after.
+impl FromWorld for PickableDescendant {
+    fn from_world(_world: &mut World) -> Self {
+        Self {
+            root: Entity::from_raw(u32::MAX),
+        }
+    }
+}
+
+impl MapEntities for PickableDescendant {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.root = entity_map.get(self.root)?;
+        Ok(())
+    }
+}
+
+/// Adds a [PickableMesh] to the meshes below every [PickableHierarchy] entity, whenever the
+/// hierarchy changes.
+#[allow(clippy::type_complexity)]
+pub fn add_pickable_descendants(
+    mut commands: Commands,
+    root_query: Query<Entity, With<PickableHierarchy>>,
+    added_root_query: Query<(), Added<PickableHierarchy>>,
+    changed_children_query: Query<(), Changed<Children>>,
+    children_query: Query<&Children>,
+    mesh_query: Query<
+        (),
+        (
+            With<Handle<Mesh>>,
+            Without<PickableMesh>,
+            Without<NotPickable>,
+        ),
+    >,
+) {
+    if added_root_query.is_empty() && changed_children_query.is_empty() {
+        return;
+    }
+    for root in root_query.iter() {
+        let mut descendants = match children_query.get(root) {
+            Ok(children) => children.to_vec(),
+            Err(_) => continue,
+        };
+        while let Some(entity) = descendants.pop() {
+            if root_query.contains(entity) {
+                continue;
+            }
+            if mesh_query.contains(entity) {
+                commands
+                    .entity(entity)
+                    .insert(PickableMesh::default())
+                    .insert(PickableDescendant { root });
+            }
+            if let Ok(children) = children_query.get(entity) {
+                descendants.extend(children.iter());
+            }
+        }
+    }
+}
+
+/// Replaces intersections with a [PickableDescendant] by intersections with its root, keeping only
+/// the nearest intersection with each root.
+pub fn retarget_pickable_descendants(
+    descendant_query: Query<&PickableDescendant>,
+    mut pick_source_query: Query<&mut PickingCamera>,
+) {
+    if descendant_query.is_empty() {
+        return;
+    }
+    for mut pick_source in pick_source_query.iter_mut() {
+        if !pick_source
+            .intersections()
+            .iter()
+            .any(|(entity, _)| descendant_query.contains(*entity))
+        {
+            continue;
+        }
+        let intersections = pick_source.intersections_mut();
+        for (entity, _) in intersections.iter_mut() {
+            if let Ok(descendant) = descendant_query.get(*entity) {
+                *entity = descendant.root;
+            }
+        }
+        let mut seen = HashSet::default();
+        intersections.retain(|(entity, _)| seen.insert(*entity));
+    }
+}
@@ -1,9 +1,10 @@
 use super::selection::*;
-use crate::PausedForBlockers;
+use crate::{PausedForBlockers, PickingPluginsState};
 use bevy::{asset::Asset, prelude::*, render::color::Color};
 
 /// Marker component to flag an entity as highlightable
-#[derive(Component, Clone, Debug, Default)]
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component)]
 pub struct Highlight;
 
 /// Component used to track the initial asset of a highlightable object, as well as for overriding
@@ -99,6 +100,35 @@ pub fn get_initial_mesh_highlight_asset<T: Asset>(
     }
 }
 
+/// Picks up material handles changed outside of highlighting, e.g. a team color swap, and stores
+/// them as the initial asset so they aren't reverted when the entity is unhovered or deselected.
+#[allow(clippy::type_complexity)]
+pub fn refresh_initial_highlight_asset<T: 'static + Highlightable + Send + Sync>(
+    global_default_highlight: Res<DefaultHighlighting<T>>,
+    mut highlight_query: Query<
+        (
+            &Handle<T::HighlightAsset>,
+            &mut Highlighting<T::HighlightAsset>,
+        ),
+        Changed<Handle<T::HighlightAsset>>,
+    >,
+) {
+    for (material, mut highlight) in highlight_query.iter_mut() {
+        let is_highlight_asset = [&highlight.hovered, &highlight.pressed, &highlight.selected]
+            .into_iter()
+            .flatten()
+            .chain([
+                &global_default_highlight.hovered,
+                &global_default_highlight.pressed,
+                &global_default_highlight.selected,
+            ])
+            .any(|highlight_asset| highlight_asset == material);
+        if !is_highlight_asset && highlight.initial != *material {
+            highlight.initial = material.to_owned();
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub fn mesh_highlighting<T: 'static + Highlightable + Send + Sync>(
     paused: Option<Res<PausedForBlockers>>,
@@ -117,7 +147,7 @@ pub fn mesh_highlighting<T: 'static + Highlightable + Send + Sync>(
     if let Some(paused) = paused {
         if paused.is_paused() {
             for (_, mut material, selection, highlight) in interaction_query.iter_mut() {
-                *material = if selection.filter(|s| s.selected()).is_some() {
+                let new_material = if selection.filter(|s| s.selected()).is_some() {
                     if let Some(highlight_asset) = &highlight.selected {
                         highlight_asset
                     } else {
@@ -125,14 +155,14 @@ pub fn mesh_highlighting<T: 'static + Highlightable + Send + Sync>(
                     }
                 } else {
                     &highlight.initial
-                }
-                .to_owned();
+                };
+                set_if_different(&mut material, new_material);
             }
             return;
         }
     }
     for (interaction, mut material, selection, highlight) in interaction_query.iter_mut() {
-        *material = match *interaction {
+        let new_material = match *interaction {
             Interaction::Clicked => {
                 if let Some(highlight_asset) = &highlight.pressed {
                     highlight_asset
@@ -158,7 +188,29 @@ pub fn mesh_highlighting<T: 'static + Highlightable + Send + Sync>(
                     &highlight.initial
                 }
             }
-        }
-        .to_owned();
+        };
+        set_if_different(&mut material, new_material);
+    }
+}
+
+/// Only writes the handle when it actually changes, so `Changed<Handle<T>>` isn't triggered every
+/// time the interaction state is re-evaluated.
+fn set_if_different<T: Asset>(material: &mut Mut<Handle<T>>, new_material: &Handle<T>) {
+    if **material != *new_material {
+        **material = new_material.to_owned();
+    }
+}
+
+/// Restores the initial asset of every highlightable entity when highlighting is disabled with
+/// [PickingPluginsState], so nothing is left stuck in a hovered or selected state.
+pub fn restore_initial_highlight_asset<T: Asset>(
+    state: Res<PickingPluginsState>,
+    mut highlight_query: Query<(&mut Handle<T>, &Highlighting<T>)>,
+) {
+    if !state.is_changed() || state.enable_highlighting {
+        return;
+    }
+    for (mut material, highlight) in highlight_query.iter_mut() {
+        set_if_different(&mut material, &highlight.initial);
     }
 }
@@ -0,0 +1,256 @@
+use crate::{NotPickable, PickableMesh, PickingCamera, Ray3d, SimplifiedMesh};
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_mod_raycast::{ray_intersection_over_mesh, Backfaces, IntersectionData};
+
+/// A [SystemParam] for looking up the current intersections of individual entities, across all
+/// [PickingCamera]s.
+///
+/// ```ignore
+/// fn my_system(hits: PickingHits, query: Query<Entity, With<MyThing>>) {
+///     for entity in query.iter() {
+///         if let Some(intersection) = hits.get(entity) {
+///             info!("{:?} was hit at {}", entity, intersection.position());
+///         }
+///     }
+/// }
+/// ```
+#[derive(SystemParam)]
+pub struct PickingHits<'w, 's> {
+    pick_sources: Query<'w, 's, (Entity, &'static PickingCamera)>,
+}
+
+impl<'w, 's> PickingHits<'w, 's> {
+    /// The nearest intersection with `entity` from any [PickingCamera].
+    pub fn get(&self, entity: Entity) -> Option<&IntersectionData> {
+        self.hits(entity).min_by(|a, b| {
+            a.distance()
+                .partial_cmp(&b.distance())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+    }
+
+    /// All intersections with `entity`, from every [PickingCamera].
+    pub fn hits(&self, entity: Entity) -> impl Iterator<Item = &IntersectionData> {
+        self.pick_sources
+            .iter()
+            .flat_map(|(_, pick_source)| pick_source.intersections())
+            .filter(move |(hit_entity, _)| *hit_entity == entity)
+            .map(|(_, intersection)| intersection)
+    }
+
+    /// Returns `true` if any [PickingCamera] intersects `entity`.
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.hits(entity).next().is_some()
+    }
+
+    /// The nearest entity intersected by the [PickingCamera] on the `source` entity.
+    pub fn top_entity(&self, source: Entity) -> Option<Entity> {
+        let (_, pick_source) = self.pick_sources.get(source).ok()?;
+        pick_source
+            .intersections()
+            .first()
+            .map(|(entity, _)| *entity)
+    }
+}
+
+/// A [SystemParam] for casting rays against every [PickableMesh] on demand, without a
+/// [PickingCamera]. Meshes are tested the same way a [PickingCamera] created with
+/// [`new_transform_empty`](PickingCamera::new_transform_empty) tests them, including
+/// [SimplifiedMesh]es and skipping meshes that aren't [Visibility::is_visible], but none of a pick
+/// source's filters, like [MaxPickDistance](crate::MaxPickDistance), are applied.
+///
+/// The ray of a [PickingCamera] is available with [`ray`](PickingCamera::ray).
+///
+/// ```ignore
+/// fn line_of_sight(raycast: PickingRaycast, query: Query<&GlobalTransform, With<Enemy>>) {
+///     for transform in query.iter() {
+///         let ray = Ray3d::new(transform.translation(), Vec3::NEG_Z);
+///         if let Some((entity, _)) = raycast.cast(ray).first() {
+///             info!("Enemy can see {:?}", entity);
+///         }
+///     }
+/// }
+/// ```
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct PickingRaycast<'w, 's> {
+    meshes: Res<'w, Assets<Mesh>>,
+    mesh_query: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Handle<Mesh>,
+            Option<&'static SimplifiedMesh>,
+            &'static GlobalTransform,
+            &'static Visibility,
+        ),
+        (With<PickableMesh>, Without<NotPickable>),
+    >,
+}
+
+impl<'w, 's> PickingRaycast<'w, 's> {
+    /// Casts `ray` against every visible [PickableMesh], returning the nearest intersection with
+    /// each mesh that was hit, sorted from nearest to farthest.
+    ///
+    /// Triangles facing away from the ray are not hit, the same as in the ray cast of a
+    /// [PickingCamera], which culls backfaces by default. Use [`cast_with`](Self::cast_with) to
+    /// hit them too, e.g. from inside a mesh.
+    pub fn cast(&self, ray: Ray3d) -> Vec<(Entity, IntersectionData)> {
+        self.cast_with(ray, Backfaces::Cull)
+    }
+
+    /// Like [`cast`](Self::cast), but with control over whether triangles facing away from the ray
+    /// are hit.
+    pub fn cast_with(&self, ray: Ray3d, backfaces: Backfaces) -> Vec<(Entity, IntersectionData)> {
+        let mut hits = self
+            .mesh_query
+            .iter()
+            .filter(|(.., visibility)| visibility.is_visible)
+            .filter_map(|(entity, mesh, simplified_mesh, transform, _)| {
+                let handle = simplified_mesh.map_or(mesh, |simplified| &simplified.mesh);
+                let mesh = self.meshes.get(handle)?;
+                ray_intersection_over_mesh(mesh, &transform.compute_matrix(), &ray, backfaces)
+                    .map(|intersection| (entity, intersection))
+            })
+            .collect::<Vec<_>>();
+        hits.sort_by(|a, b| {
+            a.1.distance()
+                .partial_cmp(&b.1.distance())
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.0.cmp(&b.0))
+        });
+        hits
+    }
+}
+
+/// Projects a world space point, like an [IntersectionData::position], into the viewport of
+/// `camera`. The result is in logical pixels, with the origin in the bottom left corner of the
+/// viewport, the same as cursor positions. Returns `None` if the point is behind the camera or
+/// outside of the viewport.
+pub fn screen_position(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    position: Vec3,
+) -> Option<Vec2> {
+    let size = camera.logical_viewport_size()?;
+    camera
+        .world_to_viewport(camera_transform, position)
+        .filter(|screen| screen.cmpge(Vec2::ZERO).all() && screen.cmple(size).all())
+}
+
+/// The normalized device depth of a world space point, like an [IntersectionData::position], as
+/// seen by `camera`, which is what ends up in the depth buffer. Returns `None` if the point is in
+/// front of the near plane or, with an orthographic projection, behind the far plane.
+///
+/// Depth is reversed: it is `1.0` on the near plane and decreases towards `0.0` with distance.
+/// With an orthographic projection it falls linearly to `0.0` at the far plane. A perspective
+/// projection has no far cutoff in the depth buffer, even though it has a `far` field: depth is
+/// `near / d`, where `d` is the distance along the camera's view direction, so it approaches
+/// `0.0` but is never `None` for points beyond `far`. Unlike [IntersectionData::distance], this
+/// ignores how far the point is off the center of the view, so two points at the same depth can
+/// be at different distances.
+pub fn ndc_depth(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    position: Vec3,
+) -> Option<f32> {
+    projected_depth(camera.projection_matrix(), camera_transform, position)
+}
+
+fn projected_depth(
+    projection_matrix: Mat4,
+    camera_transform: &GlobalTransform,
+    position: Vec3,
+) -> Option<f32> {
+    let world_to_ndc = projection_matrix * camera_transform.compute_matrix().inverse();
+    Some(world_to_ndc.project_point3(position).z).filter(|depth| (0.0..=1.0).contains(depth))
+}
+
+/// The nearest intersection of any [PickingCamera] with `entity`.
+pub(crate) fn entity_intersection(
+    pick_source_query: &Query<&PickingCamera>,
+    entity: Entity,
+) -> Option<IntersectionData> {
+    pick_source_query
+        .iter()
+        .filter_map(|pick_source| {
+            pick_source
+                .intersect_list()?
+                .iter()
+                .find(|(hit_entity, _)| *hit_entity == entity)
+                .map(|(_, intersection)| intersection)
+        })
+        .min_by(|a, b| a.distance().total_cmp(&b.distance()))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replace_intersections;
+    use bevy::{ecs::system::SystemState, render::camera::CameraProjection};
+
+    #[test]
+    fn entity_intersection_is_the_nearest_of_all_pick_sources() {
+        let mut world = World::new();
+        let entity = world.spawn().id();
+        for distance in [5.0, 2.0, 8.0] {
+            let mut pick_source = PickingCamera::new();
+            let position = Vec3::new(0.0, 0.0, -distance);
+            let hit = IntersectionData::new(position, Vec3::Z, distance, None);
+            replace_intersections(&mut pick_source, |_| true, vec![(entity, hit)]);
+            world.spawn().insert(pick_source);
+        }
+
+        let mut state: SystemState<Query<&PickingCamera>> = SystemState::new(&mut world);
+        let hit = entity_intersection(&state.get(&world), entity).unwrap();
+        assert_eq!(hit.distance(), 2.0);
+    }
+
+    fn depth(projection: &impl CameraProjection, distance: f32) -> Option<f32> {
+        let position = Vec3::new(1.0, 0.5, -distance);
+        projected_depth(
+            projection.get_projection_matrix(),
+            &GlobalTransform::default(),
+            position,
+        )
+    }
+
+    fn assert_depth(actual: Option<f32>, expected: f32) {
+        let actual = actual.expect("point should be in front of the camera");
+        assert!(
+            (actual - expected).abs() < 1e-6,
+            "{} != {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn perspective_depth() {
+        let projection = PerspectiveProjection {
+            near: 0.5,
+            far: 100.0,
+            ..Default::default()
+        };
+        assert_depth(depth(&projection, 0.5), 1.0);
+        assert_depth(depth(&projection, 2.0), 0.25);
+        // There is no far cutoff.
+        assert_depth(depth(&projection, 500.0), 0.001);
+        assert_eq!(depth(&projection, 0.25), None);
+    }
+
+    #[test]
+    fn orthographic_depth() {
+        let projection = OrthographicProjection {
+            near: 0.0,
+            far: 100.0,
+            ..Default::default()
+        };
+        assert_depth(depth(&projection, 25.0), 0.75);
+        assert_depth(depth(&projection, 90.0), 0.1);
+        assert_eq!(depth(&projection, 150.0), None);
+        assert_eq!(depth(&projection, -1.0), None);
+    }
+}
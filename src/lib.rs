@@ -1,40 +1,154 @@
+pub mod backend;
+pub mod box_selection;
+pub mod bubbling;
+pub mod cursor_icon;
+pub mod debug;
+pub mod diagnostics;
+pub mod drag;
 pub mod events;
+pub mod filter;
 pub mod focus;
+pub mod forwarding;
+pub mod hierarchy;
 pub mod highlight;
+pub mod hits;
 pub mod mouse;
 pub mod selection;
+pub mod sprite;
 
 pub use crate::{
-    events::{event_debug_system, mesh_events_system, HoverEvent, PickingEvent, SelectionEvent},
+    backend::replace_intersections,
+    box_selection::{
+        box_selection, update_box_selection_rect, BoxSelection, BoxSelectionRect,
+        BoxSelectionSettings,
+    },
+    bubbling::{bubble_picking_events, BubbledPickingEvent, PickingListener, StopPropagation},
+    cursor_icon::{update_hover_cursor_icon, HoverCursorIcon},
+    debug::{
+        despawn_debug_visuals, update_debug_cursor, update_debug_rays, update_debug_text,
+        DebugCursor, DebugCursorSettings, DebugRay, DebugText,
+    },
+    diagnostics::PickingDiagnosticsPlugin,
+    drag::{drag_plane_system, mesh_drag_system, DragPlane, DragSettings, DragState},
+    events::{
+        event_debug_system, hover_timeout_system, mesh_events_system, DoubleClickSettings,
+        DragEvent, HoverEvent, PickingEvent, SelectionEvent, TimedEventSettings,
+    },
+    filter::{
+        apply_frustum_clipping, apply_max_pick_distance, apply_render_layers,
+        clear_picks_when_disabled, remove_unpickable_intersections, sort_intersections,
+        ClipToFrustum, MaxPickDistance, NotPickable, PickDepthBias,
+    },
     focus::{mesh_focus, pause_for_picking_blockers, Hover, PickingBlocker},
+    forwarding::{
+        forward_picking_events, EventForwardingPlugin, ForwardPickingEvents, FromPickingEvent,
+    },
+    hierarchy::{
+        add_pickable_descendants, retarget_pickable_descendants, PickableDescendant,
+        PickableHierarchy,
+    },
     highlight::{
-        mesh_highlighting, DefaultHighlighting, Highlightable, Highlighting,
-        StandardMaterialHighlight,
+        mesh_highlighting, refresh_initial_highlight_asset, restore_initial_highlight_asset,
+        DefaultHighlighting, Highlightable, Highlighting, StandardMaterialHighlight,
+    },
+    hits::{ndc_depth, screen_position, PickingHits, PickingRaycast},
+    mouse::{
+        clear_picks_outside_viewport, update_cursor_left_windows, update_cursor_motion,
+        update_pick_source_positions, CursorLeftWindows, CursorMotion, CursorMotionEvent,
+    },
+    selection::{
+        cycle_selection, enforce_selection_groups, mesh_selection, selection_commands,
+        update_current_selection, CurrentSelection, NoDeselect, Selection, SelectionCommand,
+        SelectionGroup, SelectionSettings, SelectionShortcut,
     },
-    mouse::update_pick_source_positions,
-    selection::{mesh_selection, NoDeselect, Selection},
+    sprite::{
+        refresh_initial_sprite_color, restore_initial_sprite_color, sprite_highlighting,
+        update_sprite_intersections, DefaultSpriteHighlighting, PickableSprite,
+    },
+};
+/// Add a [SimplifiedMesh] to a [PickableMesh] entity to ray cast against a cheaper proxy mesh
+/// instead of the entity's render mesh. Intersections still report the entity, using its
+/// transform.
+pub use bevy_mod_raycast::SimplifiedMesh;
+pub use bevy_mod_raycast::{
+    Backfaces, IntersectionData, Primitive3d, Ray3d, RayCastMethod, RayCastSource,
 };
-pub use bevy_mod_raycast::{Primitive3d, RayCastSource};
 
-use bevy::{app::PluginGroupBuilder, ecs::schedule::ShouldRun, prelude::*, ui::FocusPolicy};
+use bevy::{
+    app::PluginGroupBuilder, ecs::schedule::ShouldRun, input::touch::TouchInput, prelude::*,
+    ui::FocusPolicy, utils::Duration,
+};
+use debug::DebugCursorAssets;
 use highlight::{get_initial_mesh_highlight_asset, ColorMaterialHighlight, Highlight};
+use sprite::{get_initial_sprite_color, InitialSpriteColor};
 
+/// Labels for the picking systems, listed in the order they run in [CoreStage::First]. Add your
+/// own systems `.after()` a label to see this frame's results.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum PickingSystem {
     UpdatePickSourcePositions,
     BuildRays,
     UpdateRaycast,
     UpdateIntersections,
-    Highlighting,
-    Selection,
+    /// Custom hit testing backends add their intersections here, see [replace_intersections]. The
+    /// built-in mesh ray cast is a backend too, so this label always exists.
+    Backend,
+    /// Every system that filters or reorders intersections, after all backends ran.
+    FilterIntersections,
+    /// Removes intersections that can't be picked, e.g. with [NotPickable] or beyond the
+    /// [MaxPickDistance].
+    CullIntersections,
+    /// Reports intersections with a [PickableDescendant] as intersections with its root.
+    RetargetIntersections,
+    /// Sorts intersections by biased depth, after every other filter.
+    SortIntersections,
     PauseForBlockers,
     Focus,
+    Selection,
+    /// [mesh_selection], which applies clicks. Selection systems that should override a click run
+    /// after it.
+    MeshSelection,
+    /// Stores the initial material or color of newly highlightable entities, before anything
+    /// refreshes or changes it.
+    InitialHighlight,
+    Highlighting,
     Events,
+    Drag,
+    Bubbling,
 }
 
 /// A type alias for the concrete [RayCastMesh](bevy_mod_raycast::RayCastMesh) type used for Picking.
+///
+/// This type comes from `bevy_mod_raycast` and doesn't implement [Reflect], so it can't be saved
+/// in a scene. Save a [Pickable] instead, which adds it back when the scene is spawned.
 pub type PickableMesh = bevy_mod_raycast::RayCastMesh<PickingRaycastSet>;
+
+/// Makes an entity's mesh pickable, by adding a [PickableMesh] when this is added. Unlike
+/// [PickableMesh], this can be reflected, so pickable entities can be saved in and loaded from
+/// scenes. It is included in the [PickableBundle].
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct Pickable;
+
+/// Adds a [PickableMesh] to entities with a new [Pickable], e.g. when a scene is spawned.
+pub fn add_pickable_meshes(
+    mut commands: Commands,
+    query: Query<Entity, (Added<Pickable>, Without<PickableMesh>)>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).insert(PickableMesh::default());
+    }
+}
+
 /// A type alias for the concrete [RayCastSource](bevy_mod_raycast::RayCastSource) type used for Picking.
+///
+/// Like [PickableMesh], this doesn't implement [Reflect], so pick sources can't be saved in a
+/// scene and need to be added in code, e.g. to the camera after the scene is spawned.
+///
+/// Picking sources don't need to be cameras. A source created with
+/// [`PickingCamera::new_transform_empty`] casts its ray along the entity's [`GlobalTransform`]
+/// instead of through the cursor, which is useful for VR controllers, turrets, or any other ray you
+/// compute yourself: just update the entity's [`Transform`] every frame.
 pub type PickingCamera = bevy_mod_raycast::RayCastSource<PickingRaycastSet>;
 
 /// This unit struct is used to tag the generic ray casting types `RayCastMesh` and
@@ -61,6 +175,72 @@ impl Default for PickingPluginsState {
     }
 }
 
+/// Resource that configures how often pick sources cast their rays, added by the [PickingPlugin].
+#[derive(Clone, Debug, Default, Resource)]
+pub struct PickingSettings {
+    pub update: PickingUpdatePolicy,
+}
+
+/// How often the hit testing backends run. When they don't run, every [PickingCamera] keeps the
+/// intersections they found last time, so hover and selection stay the same in between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PickingUpdatePolicy {
+    /// Cast rays every frame.
+    EveryFrame,
+    /// Only cast rays in frames where the cursor moved or a touch input happened. Entities moving
+    /// under a resting pointer are not picked up until the pointer moves.
+    OnEvent,
+    /// Cast rays at most once per frame, and only after this much time has passed since the
+    /// last cast, like a fixed timestep.
+    Interval(Duration),
+}
+
+impl Default for PickingUpdatePolicy {
+    fn default() -> Self {
+        PickingUpdatePolicy::EveryFrame
+    }
+}
+
+/// Label of the run criteria that decides whether hit testing backends cast their rays this frame,
+/// according to the [PickingSettings] update policy. Custom backends should run in a
+/// [SystemSet] with `.with_run_criteria(UpdatePicksCriteria)`, so they are throttled like the
+/// built-in ones.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RunCriteriaLabel)]
+pub struct UpdatePicksCriteria;
+
+/// Runs the backends according to the [PickingSettings] update policy.
+fn update_picks_criteria(
+    mut elapsed: Local<f64>,
+    state: Res<PickingPluginsState>,
+    settings: Res<PickingSettings>,
+    time: Res<Time>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut touch_input: EventReader<TouchInput>,
+) -> ShouldRun {
+    // Always read the events, so switching to `OnEvent` doesn't react to stale ones.
+    let input = cursor_moved.iter().count() + touch_input.iter().count() > 0;
+    let update = match settings.update {
+        PickingUpdatePolicy::EveryFrame => true,
+        PickingUpdatePolicy::OnEvent => input,
+        PickingUpdatePolicy::Interval(interval) => {
+            let interval = interval.as_secs_f64();
+            *elapsed += time.delta_seconds_f64();
+            if *elapsed < interval {
+                false
+            } else {
+                // Carry over the remainder, but never catch up with more than one cast per frame.
+                *elapsed = if interval > 0.0 {
+                    *elapsed % interval
+                } else {
+                    0.0
+                };
+                true
+            }
+        }
+    };
+    simple_criteria(state.enable_picking && update)
+}
+
 fn simple_criteria(flag: bool) -> ShouldRun {
     if flag {
         ShouldRun::Yes
@@ -77,9 +257,17 @@ impl PausedForBlockers {
     }
 }
 
+/// When a [PickingCamera] driven by the cursor updates its ray. This only decides where the ray
+/// points: the ray is cast every frame either way, so a resting cursor keeps re-testing the meshes
+/// under it. To skip ray casts in frames without cursor or touch input, set the [PickingSettings]
+/// update policy to [PickingUpdatePolicy::OnEvent].
 #[derive(Component, Debug, Clone, Copy)]
 pub enum UpdatePicks {
+    /// Point the ray at the latest cursor position every frame, or at the last known position
+    /// if the cursor didn't move.
     EveryFrame(Vec2),
+    /// Only point the ray at the cursor in frames where it moved, and otherwise keep the ray as
+    /// it is.
     OnMouseEvent,
 }
 impl Default for UpdatePicks {
@@ -88,6 +276,7 @@ impl Default for UpdatePicks {
     }
 }
 
+/// Adds the [PickingPlugin], [InteractablePickingPlugin] and [HighlightablePickingPlugins].
 pub struct DefaultPickingPlugins;
 
 impl PluginGroup for DefaultPickingPlugins {
@@ -98,21 +287,100 @@ impl PluginGroup for DefaultPickingPlugins {
     }
 }
 
+/// Finds the [PickableMesh]es under every [PickingCamera], without any interaction or
+/// highlighting. Adding only this plugin is enough to read [PickingCamera] intersections, see the
+/// `core_only` example.
 pub struct PickingPlugin;
 impl Plugin for PickingPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PickingPluginsState>()
+            .init_resource::<PickingSettings>()
+            .init_resource::<CursorLeftWindows>()
+            .register_type::<NotPickable>()
+            .register_type::<PickDepthBias>()
+            .register_type::<PickableHierarchy>()
+            .register_type::<PickableDescendant>()
+            .register_type::<Pickable>()
+            .register_type::<MaxPickDistance>()
+            .register_type::<ClipToFrustum>()
+            .register_type::<CursorMotion>()
+            .add_event::<CursorMotionEvent>()
             .add_system_set_to_stage(
                 CoreStage::First,
                 SystemSet::new()
                     .with_run_criteria(|state: Res<PickingPluginsState>| {
                         simple_criteria(state.enable_picking)
                     })
+                    .with_system(
+                        update_cursor_left_windows
+                            .label(PickingSystem::UpdatePickSourcePositions)
+                            .before(PickingSystem::BuildRays),
+                    )
                     .with_system(
                         update_pick_source_positions
                             .label(PickingSystem::UpdatePickSourcePositions)
                             .before(PickingSystem::BuildRays),
                     )
+                    .with_system(
+                        update_cursor_motion
+                            .after(PickingSystem::UpdatePickSourcePositions)
+                            .before(PickingSystem::BuildRays),
+                    )
+                    .with_system(add_pickable_descendants.before(PickingSystem::UpdateRaycast))
+                    .with_system(add_pickable_meshes.before(PickingSystem::UpdateRaycast))
+                    .with_system(
+                        clear_picks_outside_viewport
+                            .label(PickingSystem::FilterIntersections)
+                            .label(PickingSystem::CullIntersections)
+                            .after(PickingSystem::Backend)
+                            .before(PickingSystem::RetargetIntersections),
+                    )
+                    .with_system(
+                        apply_max_pick_distance
+                            .label(PickingSystem::FilterIntersections)
+                            .label(PickingSystem::CullIntersections)
+                            .after(PickingSystem::Backend)
+                            .before(PickingSystem::RetargetIntersections),
+                    )
+                    .with_system(
+                        apply_frustum_clipping
+                            .label(PickingSystem::FilterIntersections)
+                            .label(PickingSystem::CullIntersections)
+                            .after(PickingSystem::Backend)
+                            .before(PickingSystem::RetargetIntersections),
+                    )
+                    .with_system(
+                        remove_unpickable_intersections
+                            .label(PickingSystem::FilterIntersections)
+                            .label(PickingSystem::CullIntersections)
+                            .after(PickingSystem::Backend)
+                            .before(PickingSystem::RetargetIntersections),
+                    )
+                    .with_system(
+                        apply_render_layers
+                            .label(PickingSystem::FilterIntersections)
+                            .label(PickingSystem::CullIntersections)
+                            .after(PickingSystem::Backend)
+                            .before(PickingSystem::RetargetIntersections),
+                    )
+                    .with_system(
+                        retarget_pickable_descendants
+                            .label(PickingSystem::FilterIntersections)
+                            .label(PickingSystem::RetargetIntersections)
+                            .after(PickingSystem::CullIntersections)
+                            .before(PickingSystem::SortIntersections),
+                    )
+                    .with_system(
+                        sort_intersections
+                            .label(PickingSystem::FilterIntersections)
+                            .label(PickingSystem::SortIntersections)
+                            .after(PickingSystem::RetargetIntersections),
+                    ),
+            )
+            .add_system_set_to_stage(
+                CoreStage::First,
+                SystemSet::new()
+                    .with_run_criteria(update_picks_criteria.label(UpdatePicksCriteria))
                     .with_system(
                         bevy_mod_raycast::build_rays::<PickingRaycastSet>
                             .label(PickingSystem::BuildRays)
@@ -125,8 +393,18 @@ impl Plugin for PickingPlugin {
                     )
                     .with_system(
                         bevy_mod_raycast::update_intersections::<PickingRaycastSet>
-                            .label(PickingSystem::UpdateIntersections),
+                            .label(PickingSystem::UpdateIntersections)
+                            .label(PickingSystem::Backend)
+                            .before(PickingSystem::FilterIntersections),
                     ),
+            )
+            .add_system_to_stage(
+                CoreStage::First,
+                clear_picks_when_disabled
+                    .label(PickingSystem::FilterIntersections)
+                    .label(PickingSystem::CullIntersections)
+                    .after(PickingSystem::Backend)
+                    .before(PickingSystem::RetargetIntersections),
             );
     }
 }
@@ -134,8 +412,25 @@ impl Plugin for PickingPlugin {
 pub struct InteractablePickingPlugin;
 impl Plugin for InteractablePickingPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<PausedForBlockers>()
+        app.init_resource::<PickingPluginsState>()
+            .init_resource::<PausedForBlockers>()
+            .register_type::<Selection>()
+            .register_type::<SelectionGroup>()
+            .register_type::<NoDeselect>()
+            .register_type::<Hover>()
+            .register_type::<PickingBlocker>()
+            .register_type::<PickingListener>()
+            .register_type::<StopPropagation>()
+            .register_type::<DragPlane>()
+            .init_resource::<SelectionSettings>()
+            .init_resource::<CurrentSelection>()
+            .init_resource::<DoubleClickSettings>()
+            .init_resource::<TimedEventSettings>()
+            .init_resource::<DragSettings>()
+            .init_resource::<DragState>()
             .add_event::<PickingEvent>()
+            .add_event::<BubbledPickingEvent>()
+            .add_event::<SelectionCommand>()
             .add_system_set_to_stage(
                 CoreStage::First,
                 SystemSet::new()
@@ -145,7 +440,7 @@ impl Plugin for InteractablePickingPlugin {
                     .with_system(
                         pause_for_picking_blockers
                             .label(PickingSystem::PauseForBlockers)
-                            .after(PickingSystem::UpdateIntersections),
+                            .after(PickingSystem::FilterIntersections),
                     )
                     .with_system(
                         mesh_focus
@@ -154,13 +449,51 @@ impl Plugin for InteractablePickingPlugin {
                     )
                     .with_system(
                         mesh_selection
+                            .label(PickingSystem::Selection)
+                            .label(PickingSystem::MeshSelection)
+                            .after(PickingSystem::Focus),
+                    )
+                    .with_system(
+                        selection_commands
+                            .label(PickingSystem::Selection)
+                            .after(PickingSystem::Focus),
+                    )
+                    .with_system(
+                        cycle_selection
                             .label(PickingSystem::Selection)
                             .after(PickingSystem::Focus),
                     )
+                    .with_system(
+                        enforce_selection_groups
+                            .after(PickingSystem::Selection)
+                            .before(PickingSystem::Highlighting)
+                            .before(PickingSystem::Events),
+                    )
                     .with_system(
                         mesh_events_system
                             .label(PickingSystem::Events)
                             .after(PickingSystem::Selection),
+                    )
+                    .with_system(
+                        hover_timeout_system
+                            .label(PickingSystem::Events)
+                            .after(PickingSystem::Selection),
+                    )
+                    .with_system(update_current_selection.after(PickingSystem::Events))
+                    .with_system(
+                        mesh_drag_system
+                            .label(PickingSystem::Drag)
+                            .after(PickingSystem::Events),
+                    )
+                    .with_system(
+                        drag_plane_system
+                            .after(PickingSystem::Drag)
+                            .before(PickingSystem::Bubbling),
+                    )
+                    .with_system(
+                        bubble_picking_events
+                            .label(PickingSystem::Bubbling)
+                            .after(PickingSystem::Drag),
                     ),
             );
     }
@@ -184,7 +517,9 @@ where
     T: 'static + Highlightable + Sync + Send,
 {
     fn build(&self, app: &mut App) {
-        app.init_resource::<DefaultHighlighting<T>>()
+        app.init_resource::<PickingPluginsState>()
+            .init_resource::<DefaultHighlighting<T>>()
+            .register_type::<Highlight>()
             .add_system_set_to_stage(
                 CoreStage::First,
                 SystemSet::new()
@@ -193,14 +528,120 @@ where
                     })
                     .with_system(
                         get_initial_mesh_highlight_asset::<T::HighlightAsset>
+                            .label(PickingSystem::InitialHighlight)
                             .after(PickingSystem::UpdateIntersections)
                             .before(PickingSystem::Highlighting),
                     )
+                    .with_system(
+                        refresh_initial_highlight_asset::<T>
+                            .after(PickingSystem::InitialHighlight)
+                            .before(PickingSystem::Highlighting),
+                    )
                     .with_system(
                         mesh_highlighting::<T>
                             .label(PickingSystem::Highlighting)
+                            .after(PickingSystem::Selection)
+                            .before(PickingSystem::Events),
+                    ),
+            )
+            .add_system_to_stage(
+                CoreStage::First,
+                restore_initial_highlight_asset::<T::HighlightAsset>
+                    .after(PickingSystem::Highlighting),
+            );
+    }
+}
+
+/// Adds picking and highlighting for [PickableSprite]s. Sprites are picked by the same pick
+/// sources and on the same schedule as meshes, so this builds on the [PickingPlugin] and adds it
+/// if it hasn't been added yet. Add this after the [PickingPlugin] or [DefaultPickingPlugins].
+pub struct SpritePickingPlugin;
+impl Plugin for SpritePickingPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PickingPlugin>() {
+            app.add_plugin(PickingPlugin);
+        }
+        app.init_resource::<DefaultSpriteHighlighting>()
+            .register_type::<PickableSprite>()
+            .register_type::<InitialSpriteColor>()
+            .add_system_set_to_stage(
+                CoreStage::First,
+                SystemSet::new()
+                    .with_run_criteria(UpdatePicksCriteria)
+                    .with_system(
+                        update_sprite_intersections
+                            .label(PickingSystem::Backend)
+                            .after(PickingSystem::UpdateIntersections),
+                    ),
+            )
+            .add_system_set_to_stage(
+                CoreStage::First,
+                SystemSet::new()
+                    .with_run_criteria(|state: Res<PickingPluginsState>| {
+                        simple_criteria(state.enable_highlighting)
+                    })
+                    .with_system(
+                        get_initial_sprite_color
+                            .label(PickingSystem::InitialHighlight)
+                            .after(PickingSystem::UpdateIntersections)
+                            .before(PickingSystem::Highlighting),
+                    )
+                    .with_system(
+                        refresh_initial_sprite_color
+                            .after(PickingSystem::InitialHighlight)
+                            .before(PickingSystem::Highlighting),
+                    )
+                    .with_system(
+                        sprite_highlighting
+                            .label(PickingSystem::Highlighting)
+                            .after(PickingSystem::Selection)
                             .before(PickingSystem::Events),
                     ),
+            )
+            .add_system_to_stage(
+                CoreStage::First,
+                restore_initial_sprite_color.after(PickingSystem::Highlighting),
+            );
+    }
+}
+
+/// Adds rubber-band box selection: dragging over empty space selects everything inside the box.
+pub struct BoxSelectionPlugin;
+impl Plugin for BoxSelectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickingPluginsState>()
+            .init_resource::<SelectionSettings>()
+            .init_resource::<BoxSelectionSettings>()
+            .init_resource::<BoxSelection>()
+            .add_system_set_to_stage(
+                CoreStage::First,
+                SystemSet::new()
+                    .with_run_criteria(|state: Res<PickingPluginsState>| {
+                        simple_criteria(state.enable_interacting)
+                    })
+                    .with_system(
+                        box_selection
+                            .label(PickingSystem::Selection)
+                            .after(PickingSystem::MeshSelection),
+                    )
+                    .with_system(update_box_selection_rect.after(PickingSystem::Selection)),
+            );
+    }
+}
+
+/// Changes the cursor icon while hovering entities with a [HoverCursorIcon].
+pub struct HoverCursorIconPlugin;
+impl Plugin for HoverCursorIconPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PickingPluginsState>()
+            .register_type::<HoverCursorIcon>()
+            .add_system_set_to_stage(
+                CoreStage::First,
+                SystemSet::new()
+                    .with_run_criteria(|state: Res<PickingPluginsState>| {
+                        simple_criteria(state.enable_interacting)
+                    })
+                    .with_system(update_hover_cursor_icon.after(PickingSystem::PauseForBlockers)),
             );
     }
 }
@@ -208,11 +649,19 @@ where
 pub struct DebugCursorPickingPlugin;
 impl Plugin for DebugCursorPickingPlugin {
     fn build(&self, app: &mut App) {
-        app.add_system_to_stage(
-            CoreStage::First,
-            bevy_mod_raycast::update_debug_cursor::<PickingRaycastSet>
-                .after(PickingSystem::UpdateIntersections),
-        );
+        app.init_resource::<DebugCursorSettings>()
+            .init_resource::<DebugCursorAssets>()
+            .add_system_set_to_stage(
+                CoreStage::First,
+                SystemSet::new()
+                    .with_run_criteria(|settings: Res<DebugCursorSettings>| {
+                        simple_criteria(settings.enabled)
+                    })
+                    .with_system(update_debug_cursor.after(PickingSystem::FilterIntersections))
+                    .with_system(update_debug_rays.after(PickingSystem::FilterIntersections))
+                    .with_system(update_debug_text.after(PickingSystem::FilterIntersections)),
+            )
+            .add_system_to_stage(CoreStage::First, despawn_debug_visuals);
     }
 }
 
@@ -221,7 +670,7 @@ impl Plugin for DebugEventsPickingPlugin {
     fn build(&self, app: &mut App) {
         app.add_system_to_stage(
             CoreStage::First,
-            event_debug_system.after(PickingSystem::Events),
+            event_debug_system.after(PickingSystem::Drag),
         );
     }
 }
@@ -230,6 +679,7 @@ impl Plugin for DebugEventsPickingPlugin {
 pub struct PickingCameraBundle {
     pub source: PickingCamera,
     pub update: UpdatePicks,
+    pub motion: CursorMotion,
 }
 
 impl Default for PickingCameraBundle {
@@ -237,12 +687,14 @@ impl Default for PickingCameraBundle {
         PickingCameraBundle {
             source: PickingCamera::new(),
             update: UpdatePicks::default(),
+            motion: CursorMotion::default(),
         }
     }
 }
 
 #[derive(Bundle, Default)]
 pub struct PickableBundle {
+    pub pickable: Pickable,
     pub pickable_mesh: PickableMesh,
     pub interaction: Interaction,
     pub focus_policy: FocusPolicy,
@@ -2,10 +2,21 @@ use crate::{PickingCamera, UpdatePicks};
 use bevy::{
     prelude::*,
     render::camera::{Camera, RenderTarget},
+    utils::{HashMap, HashSet},
+    window::{CursorLeft, WindowFocused, WindowId},
 };
 use bevy_mod_raycast::RayCastMethod;
 
-/// Update Screenspace ray cast sources with the current mouse position
+/// Update Screenspace ray cast sources with the current mouse position.
+///
+/// Cursor and touch positions are both reported by Bevy in logical pixels, with the origin in the
+/// bottom left corner of the window. They are passed straight through to the ray cast source,
+/// which converts them to NDC relative to the camera's logical viewport rect, so the window's
+/// scale factor is accounted for on HiDPI displays, and a camera that renders to part of the
+/// window, e.g. in split-screen, casts its ray through the cursor's position in its own viewport.
+///
+/// Cursor events are read once per frame, keeping the latest position in each window, so every
+/// source on the same window sees the same position regardless of how many sources there are.
 pub fn update_pick_source_positions(
     touches_input: Res<Touches>,
     mut cursor: EventReader<CursorMoved>,
@@ -15,15 +26,21 @@ pub fn update_pick_source_positions(
         Option<&Camera>,
     )>,
 ) {
+    let cursor_moved: HashMap<WindowId, Vec2> = cursor
+        .iter()
+        .map(|cursor_moved| (cursor_moved.id, cursor_moved.position))
+        .collect();
     for (mut pick_source, option_update_picks, option_camera) in &mut pick_source_query.iter_mut() {
         let (mut update_picks, cursor_latest) = match get_inputs(
             option_camera,
             option_update_picks,
-            &mut cursor,
+            &cursor_moved,
             &touches_input,
         ) {
             Some(value) => value,
-            None => return,
+            // Sources without a camera, e.g. those using `RayCastMethod::Transform`, are not
+            // driven by the cursor.
+            None => continue,
         };
         match *update_picks {
             UpdatePicks::EveryFrame(cached_cursor_pos) => {
@@ -48,30 +65,170 @@ pub fn update_pick_source_positions(
 fn get_inputs<'a>(
     option_camera: Option<&Camera>,
     option_update_picks: Option<Mut<'a, UpdatePicks>>,
-    cursor: &mut EventReader<CursorMoved>,
+    cursor_moved: &HashMap<WindowId, Vec2>,
     touches_input: &Res<Touches>,
 ) -> Option<(Mut<'a, UpdatePicks>, Option<Vec2>)> {
     let camera = option_camera?;
     let update_picks = option_update_picks?;
+    // Touch positions are in logical pixels with the origin in the top left, so they are flipped
+    // using the logical (not physical) height of the render target to match `CursorMoved`.
     let height = camera.logical_target_size()?.y;
-    let cursor_latest = match cursor.iter().last() {
-        Some(cursor_moved) => {
-            if let RenderTarget::Window(window) = camera.target {
-                if cursor_moved.id == window {
-                    Some(cursor_moved.position)
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        }
-        None => touches_input.iter().last().map(|touch| {
+    let cursor_latest = if cursor_moved.is_empty() {
+        touches_input.iter().last().map(|touch| {
             Vec2::new(
                 touch.position().x as f32,
                 height - touch.position().y as f32,
             )
-        }),
+        })
+    } else if let RenderTarget::Window(window) = camera.target {
+        cursor_moved.get(&window).copied()
+    } else {
+        None
     };
     Some((update_picks, cursor_latest))
 }
+
+/// Returns the cursor position of the first screenspace [PickingCamera], in logical pixels.
+pub(crate) fn cursor_position(pick_source_query: &Query<&PickingCamera>) -> Option<Vec2> {
+    pick_source_query
+        .iter()
+        .find_map(|pick_source| match pick_source.cast_method {
+            RayCastMethod::Screenspace(cursor) => Some(cursor),
+            _ => None,
+        })
+}
+
+/// Returns `true` if the pointer's press was cancelled this frame, because a touch was cancelled or
+/// a window lost focus. Presses that are cancelled must not turn into clicks or drops.
+pub(crate) fn press_cancelled(
+    touches_input: &Touches,
+    window_focused: &mut EventReader<WindowFocused>,
+) -> bool {
+    // Read all of the events, so none are left over for the next frame.
+    let window_unfocused = window_focused.iter().filter(|event| !event.focused).count() > 0;
+    window_unfocused || touches_input.iter_just_cancelled().next().is_some()
+}
+
+/// Windows that the cursor has left, or that have lost focus, since the cursor last moved in them.
+#[derive(Debug, Default, Resource)]
+pub struct CursorLeftWindows(HashSet<WindowId>);
+
+impl CursorLeftWindows {
+    /// Returns `true` if the cursor is not currently over the window.
+    pub fn contains(&self, window: WindowId) -> bool {
+        self.0.contains(&window)
+    }
+}
+
+/// Keeps [CursorLeftWindows] up to date. Picking resumes in a window as soon as the cursor moves in
+/// it again.
+pub fn update_cursor_left_windows(
+    mut cursor_left_windows: ResMut<CursorLeftWindows>,
+    mut cursor_moved: EventReader<CursorMoved>,
+    mut cursor_left: EventReader<CursorLeft>,
+    mut window_focused: EventReader<WindowFocused>,
+) {
+    for event in cursor_left.iter() {
+        cursor_left_windows.0.insert(event.id);
+    }
+    for event in window_focused.iter() {
+        if !event.focused {
+            cursor_left_windows.0.insert(event.id);
+        }
+    }
+    for event in cursor_moved.iter() {
+        cursor_left_windows.0.remove(&event.id);
+    }
+}
+
+/// Clears the intersections of screenspace [PickingCamera]s whose cursor is outside of the camera's
+/// viewport. This prevents cameras that only render to part of a window, e.g. in split-screen or a
+/// minimap, from picking entities when the cursor is over a different viewport, and stops
+/// entities at the edge of the window from staying hovered after the cursor has left it.
+pub fn clear_picks_outside_viewport(
+    cursor_left_windows: Res<CursorLeftWindows>,
+    mut pick_source_query: Query<(&mut PickingCamera, &Camera)>,
+) {
+    for (mut pick_source, camera) in pick_source_query.iter_mut() {
+        let cursor = match pick_source.cast_method {
+            RayCastMethod::Screenspace(cursor) => cursor,
+            _ => continue,
+        };
+        let cursor_left_window = match camera.target {
+            RenderTarget::Window(window) => cursor_left_windows.contains(window),
+            _ => false,
+        };
+        if (cursor_left_window || !cursor_in_viewport(cursor, camera))
+            && !pick_source.intersections().is_empty()
+        {
+            pick_source.intersections_mut().clear();
+        }
+    }
+}
+
+/// Returns `true` if a cursor position, in logical pixels with the origin in the bottom left of the
+/// render target, is inside the camera's viewport.
+pub(crate) fn cursor_in_viewport(cursor: Vec2, camera: &Camera) -> bool {
+    let (target_size, (viewport_min, viewport_max)) =
+        match (camera.logical_target_size(), camera.logical_viewport_rect()) {
+            (Some(target_size), Some(viewport_rect)) => (target_size, viewport_rect),
+            _ => return false,
+        };
+    // The viewport rect has its origin in the top left of the render target.
+    let cursor = Vec2::new(cursor.x, target_size.y - cursor.y);
+    cursor.cmpge(viewport_min).all() && cursor.cmple(viewport_max).all()
+}
+
+/// Tracks how far the cursor of a screenspace [PickingCamera] moved since the previous frame. Add
+/// this to the entity with the [PickingCamera] component; it is included in the
+/// [PickingCameraBundle](crate::PickingCameraBundle).
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct CursorMotion {
+    previous: Option<Vec2>,
+    delta: Option<Vec2>,
+}
+
+impl CursorMotion {
+    /// The cursor position in the previous frame, in logical pixels.
+    pub fn previous(&self) -> Option<Vec2> {
+        self.previous
+    }
+    /// How far the cursor moved since the previous frame, in logical pixels. All cursor movement
+    /// within a frame is combined. This is `None` if the cursor position isn't known for both
+    /// frames.
+    pub fn delta(&self) -> Option<Vec2> {
+        self.delta
+    }
+}
+
+/// Sent when the cursor of a pick source with a [CursorMotion] component moves.
+#[derive(Debug, Clone, Copy)]
+pub struct CursorMotionEvent {
+    pub source: Entity,
+    /// How far the cursor moved since the previous frame, in logical pixels.
+    pub delta: Vec2,
+}
+
+/// Updates [CursorMotion] from the position of screenspace pick sources, and sends
+/// [CursorMotionEvent]s.
+pub fn update_cursor_motion(
+    mut motion_events: EventWriter<CursorMotionEvent>,
+    mut pick_source_query: Query<(Entity, &PickingCamera, &mut CursorMotion)>,
+) {
+    for (source, pick_source, mut motion) in pick_source_query.iter_mut() {
+        let current = match pick_source.cast_method {
+            RayCastMethod::Screenspace(cursor) => Some(cursor),
+            _ => None,
+        };
+        let delta = match (motion.previous, current) {
+            (Some(previous), Some(current)) => Some(current - previous),
+            _ => None,
+        };
+        if let Some(delta) = delta.filter(|delta| *delta != Vec2::ZERO) {
+            motion_events.send(CursorMotionEvent { source, delta });
+        }
+        motion.previous = current;
+        motion.delta = delta;
+    }
+}
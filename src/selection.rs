@@ -1,5 +1,7 @@
-use crate::PausedForBlockers;
-use bevy::prelude::*;
+use crate::{
+    drag::topmost_entity, mouse::cursor_position, DragSettings, PausedForBlockers, PickingCamera,
+};
+use bevy::{prelude::*, utils::HashMap};
 
 /// Tracks the current selection state to be used with change tracking in the events system.
 /// Entities with [Selection] will have selection state managed.
@@ -7,7 +9,8 @@ use bevy::prelude::*;
 /// # Requirements
 ///
 /// An entity with the [Selection] component must also have an [Interaction] component.
-#[derive(Component, Debug, Default, Copy, Clone)]
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component)]
 pub struct Selection {
     selected: bool,
 }
@@ -21,20 +24,163 @@ impl Selection {
     }
 }
 
+/// Resource listing the selected entities, in the order they were selected.
+#[derive(Debug, Default, Clone, Resource)]
+pub struct CurrentSelection {
+    entities: Vec<Entity>,
+}
+
+impl CurrentSelection {
+    /// The most recently selected entity.
+    pub fn primary(&self) -> Option<Entity> {
+        self.entities.last().copied()
+    }
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.entities.contains(&entity)
+    }
+    /// The selected entities, in the order they were selected.
+    pub fn iter(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.iter().copied()
+    }
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// Resource that configures how [mesh_selection] responds to input.
+#[derive(Clone, Debug, Resource)]
+pub struct SelectionSettings {
+    /// The mouse button that selects the entity under the cursor when clicked. Set this to `None`
+    /// to disable selecting with the mouse and touch entirely, e.g. if you manage [Selection]
+    /// yourself.
+    pub button: Option<MouseButton>,
+    /// While any of these keys are held, clicking an entity toggles its selection without
+    /// deselecting anything else.
+    pub multiselect_keys: Vec<KeyCode>,
+    /// Deselect everything when clicking on empty space. Clicking a pickable entity that doesn't
+    /// have a [Selection] component counts as clicking on empty space. Pressing on empty space and
+    /// moving the pointer further than the [DragSettings] threshold before releasing it, e.g. to
+    /// orbit the camera or draw a selection box, doesn't deselect anything.
+    pub deselect_on_miss: bool,
+    /// Keyboard shortcuts that run a [SelectionCommand].
+    pub shortcuts: Vec<SelectionShortcut>,
+    /// Pressing this key moves the selection to the next visible entity with a [Selection], in
+    /// order of entity id. Keyboard cycling is disabled by default, so it doesn't take over a key
+    /// the app already uses; set this to e.g. `Some(KeyCode::Tab)` to enable it.
+    pub cycle_key: Option<KeyCode>,
+    /// While any of these keys are held, the [`cycle_key`](Self::cycle_key) moves the selection
+    /// to the previous entity instead.
+    pub cycle_reverse_keys: Vec<KeyCode>,
+}
+
+impl Default for SelectionSettings {
+    fn default() -> Self {
+        Self {
+            button: Some(MouseButton::Left),
+            multiselect_keys: vec![KeyCode::LControl, KeyCode::RControl],
+            deselect_on_miss: true,
+            shortcuts: vec![
+                SelectionShortcut {
+                    key: KeyCode::A,
+                    modifiers: vec![KeyCode::LControl, KeyCode::RControl],
+                    command: SelectionCommand::SelectAll,
+                },
+                SelectionShortcut {
+                    key: KeyCode::I,
+                    modifiers: vec![KeyCode::LControl, KeyCode::RControl],
+                    command: SelectionCommand::Invert,
+                },
+                SelectionShortcut {
+                    key: KeyCode::Escape,
+                    modifiers: Vec::new(),
+                    command: SelectionCommand::DeselectAll,
+                },
+            ],
+            cycle_key: None,
+            cycle_reverse_keys: vec![KeyCode::LShift, KeyCode::RShift],
+        }
+    }
+}
+
+/// An event that changes the selection of every entity with a [Selection] component. Entities
+/// marked with [NoDeselect] are never deselected by these commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectionCommand {
+    SelectAll,
+    DeselectAll,
+    Invert,
+}
+
+/// Runs `command` when `key` is pressed while any of the `modifiers` are held, or when `key` is
+/// pressed on its own if there are no `modifiers`.
+#[derive(Clone, Debug)]
+pub struct SelectionShortcut {
+    pub key: KeyCode,
+    pub modifiers: Vec<KeyCode>,
+    pub command: SelectionCommand,
+}
+
+/// At most one entity in a selection group can be selected at a time, like a set of radio buttons.
+/// When an entity becomes selected, the other entities with the same [SelectionGroup] are
+/// deselected, even while multiselect keys are held and even if they are marked [NoDeselect].
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Component)]
+pub struct SelectionGroup(pub u32);
+
 /// Marker struct used to mark pickable entities for which you don't want to trigger a deselection event when picked. This is useful for gizmos or other pickable UI entities.
-#[derive(Component, Debug, Copy, Clone)]
+///
+/// This also applies to all descendants of the marked entity, including children spawned later.
+#[derive(Component, Debug, Default, Copy, Clone, Reflect)]
+#[reflect(Component)]
 pub struct NoDeselect;
 
+/// Returns `true` if the entity or any of its ancestors is marked [NoDeselect].
+pub(crate) fn is_no_deselect(
+    entity: Entity,
+    hierarchy_query: &Query<(Option<&NoDeselect>, Option<&Parent>)>,
+) -> bool {
+    let mut current = Some(entity);
+    while let Some((no_deselect, parent)) = current.and_then(|e| hierarchy_query.get(e).ok()) {
+        if no_deselect.is_some() {
+            return true;
+        }
+        current = parent.map(|parent| parent.get());
+    }
+    false
+}
+
+/// A press of the selection button, applied by [mesh_selection] when the button is released.
+#[derive(Debug, Clone, Copy)]
+pub struct PendingSelection {
+    /// The selectable entity under the pointer when the button was pressed, or `None` if the press
+    /// was on empty space.
+    target: Option<Entity>,
+    /// Where the pointer was when the button was pressed.
+    position: Option<Vec2>,
+}
+
+/// Selects the entity under the pointer, or deselects everything when clicking on empty space.
+/// Selection changes when the button is released, and only if the press was a click: the pointer
+/// must not move further than the [DragSettings] threshold, and a press on an entity only selects
+/// it if the same entity is still under the pointer on release.
 #[allow(clippy::too_many_arguments)]
 pub fn mesh_selection(
+    mut pending: Local<Option<PendingSelection>>,
     paused: Option<Res<PausedForBlockers>>,
+    settings: Res<SelectionSettings>,
+    drag_settings: Res<DragSettings>,
     mouse_button_input: Res<Input<MouseButton>>,
     touches_input: Res<Touches>,
     keyboard_input: Res<Input<KeyCode>>,
-    query_changed: Query<&Interaction, (Changed<Interaction>, Without<NoDeselect>)>,
-    mut query_all: Query<(&mut Selection, &Interaction)>,
+    query_pressed: Query<(Entity, &Interaction), With<Selection>>,
+    mut query_all: Query<(Entity, &mut Selection, &Interaction)>,
     node_query: Query<&Interaction, With<Node>>,
-    no_deselect_query: Query<&Interaction, With<NoDeselect>>,
+    interaction_query: Query<(Entity, &Interaction)>,
+    hierarchy_query: Query<(Option<&NoDeselect>, Option<&Parent>)>,
+    pick_source_query: Query<&PickingCamera>,
 ) {
     if let Some(paused) = paused {
         if paused.0 {
@@ -42,63 +188,228 @@ pub fn mesh_selection(
         }
     }
 
-    // Check if something has been clicked on
-    let mut new_selection = false;
-    for interaction in query_changed.iter() {
-        if *interaction == Interaction::Clicked {
-            new_selection = true;
-        }
+    let pressed = settings.button.map_or(false, |button| {
+        mouse_button_input.just_pressed(button)
+            || touches_input.iter_just_pressed().next().is_some()
+    });
+    let released = settings.button.map_or(false, |button| {
+        mouse_button_input.just_released(button)
+            || touches_input.iter_just_released().next().is_some()
+    });
+    let position = cursor_position(&pick_source_query);
+    let multiselect = keyboard_input.any_pressed(settings.multiselect_keys.iter().copied());
+
+    if pressed {
+        // Anything under the pointer when the selection button is pressed has been pressed on.
+        let pressed_on = |interaction: &Interaction| *interaction != Interaction::None;
+        let selectable = |entity: Entity| {
+            query_pressed
+                .get(entity)
+                .map_or(false, |(_, interaction)| pressed_on(interaction))
+                && !is_no_deselect(entity, &hierarchy_query)
+        };
+        // When several selectable entities are under the pointer, e.g. with multiple pick sources
+        // or a FocusPolicy::Pass, the nearest one is selected. Pressed entities that no pick source
+        // intersects, e.g. UI nodes with a Selection, are only used if there is none.
+        let target = topmost_entity(&pick_source_query, selectable).or_else(|| {
+            query_pressed
+                .iter()
+                .map(|(entity, _)| entity)
+                .find(|entity| selectable(*entity))
+        });
+        // Deselection is not run if the UI or an item tagged with `NoDeselect` was pressed on.
+        let ui_not_pressed = !node_query.iter().any(pressed_on);
+        let no_deselect_not_pressed = !interaction_query.iter().any(|(entity, interaction)| {
+            pressed_on(interaction) && is_no_deselect(entity, &hierarchy_query)
+        });
+        *pending = if target.is_some() || (ui_not_pressed && no_deselect_not_pressed) {
+            Some(PendingSelection { target, position })
+        } else {
+            None
+        };
+    }
+
+    if !released {
+        return;
+    }
+    let PendingSelection {
+        target,
+        position: press_position,
+    } = match pending.take() {
+        Some(pending) => pending,
+        None => return,
+    };
+
+    // Moving the pointer further than the drag threshold, e.g. to drag an entity, orbit the camera
+    // or draw a selection box, is not a click, so the selection doesn't change. This is the same
+    // check that keeps the pointer from sending a `PickingEvent::Clicked`.
+    if drag_settings.is_drag(press_position, position) {
+        return;
     }
 
-    if keyboard_input.pressed(KeyCode::LControl) && keyboard_input.pressed(KeyCode::A) {
-        // The user has hit ctrl+a, select all the things!
-        query_all.for_each_mut(|(mut selection, _)| {
-            if !selection.selected {
-                selection.selected = true;
+    match target {
+        Some(target) => {
+            // The press only counts as a click if it is released over the entity it started on.
+            let still_hovered = query_all.get(target).map_or(false, |(_, _, interaction)| {
+                *interaction != Interaction::None
+            });
+            if !still_hovered {
+                return;
             }
-        });
-    } else if new_selection {
-        // Some pickable mesh has been clicked on - figure out what to select or deselect
-        for (mut selection, interaction) in &mut query_all.iter_mut() {
-            if selection.selected
-                && *interaction != Interaction::Clicked
-                && !keyboard_input.pressed(KeyCode::LControl)
-            {
-                // In this case, the entity is currently marked as selected, but it was not clicked
-                // on (interaction), and lctrl was not being held, so it should be deselected.
-                selection.selected = false;
-            } else if *interaction == Interaction::Clicked
-                && keyboard_input.pressed(KeyCode::LControl)
-            {
-                selection.selected = !selection.selected
-            } else if !selection.selected && *interaction == Interaction::Clicked {
-                selection.selected = true;
+            for (entity, mut selection, _interaction) in query_all.iter_mut() {
+                let clicked = entity == target;
+                if selection.selected && !clicked && !multiselect {
+                    // In this case, the entity is currently marked as selected, but it was not
+                    // clicked on, and multiselect was not being held, so it should be deselected.
+                    selection.selected = false;
+                } else if clicked && multiselect {
+                    selection.selected = !selection.selected
+                } else if !selection.selected && clicked {
+                    selection.selected = true;
+                }
             }
         }
-    } else if !keyboard_input.pressed(KeyCode::LControl) {
-        // This branch deselects everything if the user clicks, in empty space. Deselection is not
-        // run if the UI or an item tagged with `NoDeselect` was clicked on.
-        let mut ui_not_clicked = true;
-        for interaction in node_query.iter() {
-            // Check if anything in the UI is being interacted with
-            if *interaction == Interaction::Clicked {
-                ui_not_clicked = false;
-            }
+        None if !multiselect && settings.deselect_on_miss => deselect_all(&mut query_all),
+        None => {}
+    }
+}
+
+fn deselect_all(query: &mut Query<(Entity, &mut Selection, &Interaction)>) {
+    for (_entity, mut selection, _interaction) in query.iter_mut() {
+        if selection.selected {
+            selection.selected = false;
         }
-        let mut no_deselect_not_clicked = true;
-        for interaction in no_deselect_query.iter() {
-            if *interaction == Interaction::Clicked {
-                no_deselect_not_clicked = false;
+    }
+}
+
+/// Applies [SelectionCommand] events, and sends them when one of the [SelectionSettings] shortcuts
+/// is pressed.
+pub fn selection_commands(
+    settings: Res<SelectionSettings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut command_events: EventReader<SelectionCommand>,
+    mut query_all: Query<(Entity, &mut Selection)>,
+    hierarchy_query: Query<(Option<&NoDeselect>, Option<&Parent>)>,
+) {
+    let shortcuts = settings.shortcuts.iter().filter(|shortcut| {
+        keyboard_input.just_pressed(shortcut.key)
+            && (shortcut.modifiers.is_empty()
+                || keyboard_input.any_pressed(shortcut.modifiers.iter().copied()))
+    });
+    let commands: Vec<SelectionCommand> = command_events
+        .iter()
+        .copied()
+        .chain(shortcuts.map(|shortcut| shortcut.command))
+        .collect();
+
+    for command in commands {
+        for (entity, mut selection) in query_all.iter_mut() {
+            let selected = match command {
+                SelectionCommand::SelectAll => true,
+                SelectionCommand::DeselectAll => false,
+                SelectionCommand::Invert => !selection.selected,
+            };
+            if selection.selected != selected
+                && (selected || !is_no_deselect(entity, &hierarchy_query))
+            {
+                selection.selected = selected;
             }
         }
-        let mouse_clicked = mouse_button_input.just_pressed(MouseButton::Left)
-            || touches_input.iter_just_pressed().next().is_some();
-        if mouse_clicked && ui_not_clicked && no_deselect_not_clicked {
-            for (mut selection, _interaction) in &mut query_all.iter_mut() {
-                if selection.selected {
-                    selection.selected = false;
-                }
+    }
+}
+
+/// Deselects the other members of a [SelectionGroup] when one of them becomes selected. If several
+/// members become selected in the same frame, e.g. with [SelectionCommand::SelectAll], the one with
+/// the lowest [Entity] stays selected, so the result doesn't depend on query iteration order.
+pub fn enforce_selection_groups(
+    mut query: Query<(
+        Entity,
+        &mut Selection,
+        &SelectionGroup,
+        ChangeTrackers<Selection>,
+    )>,
+) {
+    let mut newly_selected: HashMap<SelectionGroup, Entity> = HashMap::default();
+    for (entity, selection, group, tracker) in query.iter() {
+        if tracker.is_changed() && selection.selected {
+            newly_selected
+                .entry(*group)
+                .and_modify(|kept| *kept = (*kept).min(entity))
+                .or_insert(entity);
+        }
+    }
+    if newly_selected.is_empty() {
+        return;
+    }
+    for (entity, mut selection, group, _) in query.iter_mut() {
+        match newly_selected.get(group) {
+            Some(selected) if *selected != entity && selection.selected => {
+                selection.selected = false;
             }
+            _ => (),
+        }
+    }
+}
+
+/// Keeps [CurrentSelection] in sync with the [Selection] components. The resource is only
+/// mutated when the selection actually changes, so it can be used with change detection.
+pub fn update_current_selection(
+    mut current_selection: ResMut<CurrentSelection>,
+    changed_query: Query<(Entity, &Selection), Changed<Selection>>,
+    removed: RemovedComponents<Selection>,
+) {
+    for entity in removed.iter() {
+        if current_selection.contains(entity) {
+            current_selection.entities.retain(|e| *e != entity);
+        }
+    }
+    for (entity, selection) in changed_query.iter() {
+        let listed = current_selection.contains(entity);
+        if selection.selected && !listed {
+            current_selection.entities.push(entity);
+        } else if !selection.selected && listed {
+            current_selection.entities.retain(|e| *e != entity);
+        }
+    }
+}
+
+/// Moves the selection to the next or previous visible entity when the [SelectionSettings] cycle
+/// key is pressed, wrapping around at the ends.
+pub fn cycle_selection(
+    settings: Res<SelectionSettings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    current_selection: Res<CurrentSelection>,
+    mut query: Query<(Entity, &mut Selection, Option<&ComputedVisibility>)>,
+) {
+    match settings.cycle_key {
+        Some(key) if keyboard_input.just_pressed(key) => (),
+        _ => return,
+    }
+    let mut candidates: Vec<Entity> = query
+        .iter()
+        .filter(|(_, _, visibility)| visibility.map_or(true, |v| v.is_visible()))
+        .map(|(entity, ..)| entity)
+        .collect();
+    if candidates.is_empty() {
+        return;
+    }
+    candidates.sort();
+
+    let current = current_selection
+        .primary()
+        .and_then(|primary| candidates.iter().position(|e| *e == primary));
+    let reverse = keyboard_input.any_pressed(settings.cycle_reverse_keys.iter().copied());
+    let next = match (current, reverse) {
+        (Some(i), false) => (i + 1) % candidates.len(),
+        (Some(i), true) => (i + candidates.len() - 1) % candidates.len(),
+        (None, false) => 0,
+        (None, true) => candidates.len() - 1,
+    };
+
+    for (entity, mut selection, _) in query.iter_mut() {
+        let selected = entity == candidates[next];
+        if selection.selected != selected {
+            selection.selected = selected;
         }
     }
 }
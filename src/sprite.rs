@@ -0,0 +1,189 @@
+use crate::{
+    backend::replace_intersections, highlight::Highlight, PausedForBlockers, PickingCamera,
+    PickingPluginsState, Selection,
+};
+use bevy::{prelude::*, render::color::Color};
+use bevy_mod_raycast::IntersectionData;
+
+/// Marker component for sprites that can be picked. Add it to a sprite along with a
+/// [PickableBundle](crate::PickableBundle), and add the [SpritePickingPlugin] to your app.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct PickableSprite;
+
+/// Resource that defines the colors sprites are tinted with when they are highlighted.
+#[derive(Clone, Debug, Resource)]
+pub struct DefaultSpriteHighlighting {
+    pub hovered: Color,
+    pub pressed: Color,
+    pub selected: Color,
+}
+
+impl Default for DefaultSpriteHighlighting {
+    fn default() -> Self {
+        Self {
+            hovered: Color::rgb(0.65, 0.65, 0.65),
+            pressed: Color::rgb(0.55, 0.85, 0.55),
+            selected: Color::rgb(0.55, 0.55, 0.9),
+        }
+    }
+}
+
+/// Component used to track the color of a highlightable sprite before it was highlighted.
+#[derive(Component, Debug, Default, Clone, Copy, Reflect)]
+#[reflect(Component)]
+pub struct InitialSpriteColor(pub Color);
+
+/// Adds the intersections of each [PickingCamera]'s ray with [PickableSprite]s to the camera's
+/// intersection list, ordered by distance along with any mesh intersections. The sprite's size,
+/// anchor, and transform are taken into account. With a 2D camera, sprites with a higher z are
+/// closer to the camera, so the topmost intersection matches the render order.
+#[allow(clippy::type_complexity)]
+pub fn update_sprite_intersections(
+    images: Res<Assets<Image>>,
+    mut pick_source_query: Query<&mut PickingCamera>,
+    sprite_query: Query<
+        (
+            Entity,
+            &Sprite,
+            &Handle<Image>,
+            &GlobalTransform,
+            Option<&ComputedVisibility>,
+        ),
+        With<PickableSprite>,
+    >,
+) {
+    for mut pick_source in pick_source_query.iter_mut() {
+        let ray = match pick_source.ray() {
+            Some(ray) => ray,
+            None => continue,
+        };
+        let mut hits = Vec::new();
+        for (entity, sprite, image, transform, visibility) in sprite_query.iter() {
+            if !visibility.map_or(true, |visibility| visibility.is_visible()) {
+                continue;
+            }
+            let size = match sprite
+                .custom_size
+                .or_else(|| images.get(image).map(|image| image.size()))
+            {
+                Some(size) => size,
+                None => continue,
+            };
+            let sprite_to_world = transform.compute_matrix();
+            let world_to_sprite = sprite_to_world.inverse();
+            let origin = world_to_sprite.transform_point3(ray.origin());
+            let direction = world_to_sprite.transform_vector3(ray.direction());
+            if direction.z.abs() <= f32::EPSILON {
+                continue; // The ray is parallel to the sprite.
+            }
+            let t = -origin.z / direction.z;
+            if t < 0.0 {
+                continue; // The sprite is behind the ray.
+            }
+            let local_hit = origin + direction * t;
+            // Sprite vertices are placed at `(quad_position - anchor) * size`, where the quad
+            // spans -0.5 to 0.5 on each axis.
+            let quad_position = local_hit.truncate() / size + sprite.anchor.as_vec();
+            if quad_position.abs().cmpgt(Vec2::splat(0.5)).any() {
+                continue;
+            }
+            let position = sprite_to_world.transform_point3(local_hit);
+            let normal = sprite_to_world.transform_vector3(Vec3::Z).normalize();
+            let distance = position.distance(ray.origin());
+            hits.push((
+                entity,
+                IntersectionData::new(position, normal, distance, None),
+            ));
+        }
+        replace_intersections(
+            &mut pick_source,
+            |entity| sprite_query.contains(entity),
+            hits,
+        );
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub fn get_initial_sprite_color(
+    mut commands: Commands,
+    sprite_query: Query<(Entity, &Sprite), (Added<Highlight>, With<PickableSprite>)>,
+) {
+    for (entity, sprite) in sprite_query.iter() {
+        commands
+            .entity(entity)
+            .insert(InitialSpriteColor(sprite.color));
+    }
+}
+
+/// Picks up sprite colors changed outside of highlighting and stores them as the
+/// [InitialSpriteColor], so they aren't reverted when the sprite is unhovered or deselected.
+#[allow(clippy::type_complexity)]
+pub fn refresh_initial_sprite_color(
+    highlighting: Res<DefaultSpriteHighlighting>,
+    mut sprite_query: Query<
+        (&Sprite, &mut InitialSpriteColor),
+        (With<PickableSprite>, Changed<Sprite>),
+    >,
+) {
+    for (sprite, mut initial) in sprite_query.iter_mut() {
+        let is_highlight_color = [
+            highlighting.hovered,
+            highlighting.pressed,
+            highlighting.selected,
+        ]
+        .contains(&sprite.color);
+        if !is_highlight_color && initial.0 != sprite.color {
+            initial.0 = sprite.color;
+        }
+    }
+}
+
+/// Tints highlightable [PickableSprite]s with the [DefaultSpriteHighlighting] colors.
+#[allow(clippy::type_complexity)]
+pub fn sprite_highlighting(
+    paused: Option<Res<PausedForBlockers>>,
+    highlighting: Res<DefaultSpriteHighlighting>,
+    mut sprite_query: Query<
+        (
+            &Interaction,
+            &mut Sprite,
+            Option<&Selection>,
+            &InitialSpriteColor,
+        ),
+        (
+            With<PickableSprite>,
+            Or<(Changed<Interaction>, Changed<Selection>)>,
+        ),
+    >,
+) {
+    let paused = paused.map_or(false, |paused| paused.is_paused());
+    for (interaction, mut sprite, selection, initial) in sprite_query.iter_mut() {
+        let selected = selection.filter(|s| s.selected()).is_some();
+        let color = match *interaction {
+            Interaction::Clicked if !paused => highlighting.pressed,
+            Interaction::Hovered if !paused => highlighting.hovered,
+            _ if selected => highlighting.selected,
+            _ => initial.0,
+        };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+/// Restores the initial color of every highlightable [PickableSprite] when highlighting is disabled
+/// with [PickingPluginsState].
+pub fn restore_initial_sprite_color(
+    state: Res<PickingPluginsState>,
+    mut sprite_query: Query<(&mut Sprite, &InitialSpriteColor), With<PickableSprite>>,
+) {
+    if !state.is_changed() || state.enable_highlighting {
+        return;
+    }
+    for (mut sprite, initial) in sprite_query.iter_mut() {
+        if sprite.color != initial.0 {
+            sprite.color = initial.0;
+        }
+    }
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use bevy::{ecs::system::SystemState, prelude::*};
+use bevy_mod_picking::PickingHits;
+use common::*;
+
+#[test]
+fn custom_backend_hits_are_picked() {
+    let mut app = app();
+    let source = spawn_source(&mut app, Vec2::new(100.0, 100.0));
+    let near = spawn_pickable(&mut app);
+    let far = spawn_pickable(&mut app);
+    let missed = spawn_pickable(&mut app);
+    app.world.resource_mut::<TestHits>().0 = vec![(far, 3.0), (near, 1.0)];
+    app.update();
+
+    let mut state = SystemState::<PickingHits>::new(&mut app.world);
+    let hits = state.get_mut(&mut app.world);
+    assert_eq!(hits.top_entity(source), Some(near));
+    assert_eq!(hits.get(far).map(|hit| hit.distance()), Some(3.0));
+    assert!(!hits.contains(missed));
+
+    app.world.resource_mut::<TestHits>().0 = vec![(missed, 2.0)];
+    app.update();
+
+    let hits = state.get_mut(&mut app.world);
+    assert_eq!(hits.top_entity(source), Some(missed));
+    assert!(!hits.contains(near));
+    assert!(!hits.contains(far));
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use bevy::{prelude::*, render::camera::Viewport};
+use bevy_mod_picking::{BoxSelectionPlugin, NoDeselect, Selection, SelectionGroup};
+use common::*;
+
+fn box_app() -> App {
+    let mut app = app();
+    app.add_plugin(BoxSelectionPlugin);
+    app
+}
+
+fn selected(app: &App, entity: Entity) -> bool {
+    app.world.get::<Selection>(entity).unwrap().selected()
+}
+
+/// Spawns a selectable entity in front of the cameras, which look down -Z from the origin, so it
+/// is in the center of every viewport.
+fn spawn_in_front(app: &mut App) -> Entity {
+    let entity = spawn_pickable(app);
+    app.world
+        .entity_mut(entity)
+        .insert_bundle(TransformBundle::from_transform(Transform::from_xyz(
+            0.0, 0.0, -10.0,
+        )));
+    entity
+}
+
+/// Drags a box over empty space, from `from` to `to` in logical pixels.
+fn drag_box(app: &mut App, from: Vec2, to: Vec2) {
+    move_window_cursor(app, from);
+    app.update();
+    press(app);
+    move_window_cursor(app, to);
+    app.update();
+    release(app);
+}
+
+#[test]
+fn box_selects_entities_inside_it() {
+    let mut app = box_app();
+    spawn_window_camera(&mut app, Vec2::new(800.0, 600.0), 1.0);
+    let entity = spawn_in_front(&mut app);
+    app.update();
+
+    drag_box(&mut app, Vec2::new(350.0, 250.0), Vec2::new(450.0, 350.0));
+    assert!(selected(&app, entity));
+}
+
+#[test]
+fn box_uses_the_viewport_it_was_started_in() {
+    let mut app = box_app();
+    spawn_window(&mut app, Vec2::new(800.0, 600.0), 1.0);
+    let half = UVec2::new(400, 600);
+    for position in [UVec2::ZERO, UVec2::new(400, 0)] {
+        spawn_cursor_camera(
+            &mut app,
+            Some(Viewport {
+                physical_position: position,
+                physical_size: half,
+                ..default()
+            }),
+        );
+    }
+    let entity = spawn_in_front(&mut app);
+    app.update();
+
+    // The entity is in the center of the right viewport, which is not the center of the window.
+    drag_box(&mut app, Vec2::new(550.0, 250.0), Vec2::new(650.0, 350.0));
+    assert!(selected(&app, entity));
+}
+
+#[test]
+fn box_skips_hidden_entities() {
+    let mut app = box_app();
+    spawn_window_camera(&mut app, Vec2::new(800.0, 600.0), 1.0);
+    let visible = spawn_in_front(&mut app);
+    let hidden = spawn_in_front(&mut app);
+    // Nothing updates the visibility here, so the default stays hidden.
+    app.world
+        .entity_mut(hidden)
+        .insert(ComputedVisibility::default());
+    app.update();
+
+    drag_box(&mut app, Vec2::new(350.0, 250.0), Vec2::new(450.0, 350.0));
+    assert!(selected(&app, visible));
+    assert!(!selected(&app, hidden));
+}
+
+#[test]
+fn box_keeps_no_deselect_entities_outside_it_selected() {
+    let mut app = box_app();
+    spawn_window_camera(&mut app, Vec2::new(800.0, 600.0), 1.0);
+    let inside = spawn_in_front(&mut app);
+    let outside = spawn_pickable(&mut app);
+    app.world
+        .entity_mut(outside)
+        .insert(NoDeselect)
+        .insert_bundle(TransformBundle::from_transform(Transform::from_xyz(
+            5.0, 0.0, -10.0,
+        )));
+    app.update();
+    app.world
+        .get_mut::<Selection>(outside)
+        .unwrap()
+        .set_selected(true);
+    app.update();
+
+    drag_box(&mut app, Vec2::new(350.0, 250.0), Vec2::new(450.0, 350.0));
+    assert!(selected(&app, inside));
+    assert!(selected(&app, outside));
+}
+
+#[test]
+fn box_selects_one_entity_per_selection_group() {
+    let mut app = box_app();
+    spawn_window_camera(&mut app, Vec2::new(800.0, 600.0), 1.0);
+    let a = spawn_in_front(&mut app);
+    let b = spawn_in_front(&mut app);
+    for entity in [a, b] {
+        app.world.entity_mut(entity).insert(SelectionGroup(0));
+    }
+    app.update();
+
+    drag_box(&mut app, Vec2::new(350.0, 250.0), Vec2::new(450.0, 350.0));
+    assert!(selected(&app, a));
+    assert!(!selected(&app, b));
+}
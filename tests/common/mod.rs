@@ -0,0 +1,198 @@
+//! A headless app for testing picking without a window, GPU, or real ray casts.
+//!
+//! [app] uses [PickingUpdatePolicy::OnEvent], so the mesh ray cast only runs in frames with cursor
+//! or touch input, e.g. after [move_window_cursor]. The entities have no meshes, so the cast finds
+//! nothing either way: the hits come from [test_backend] instead, a minimal custom backend that
+//! injects the entities in [TestHits] into every pick source with [replace_intersections] after
+//! the cast.
+#![allow(dead_code)]
+
+use bevy::{
+    asset::AssetPlugin,
+    hierarchy::HierarchyPlugin,
+    input::InputPlugin,
+    prelude::*,
+    render::camera::{camera_system, Projection, Viewport},
+    transform::TransformPlugin,
+    window::{WindowId, WindowPlugin},
+};
+use bevy_mod_picking::{
+    replace_intersections, InteractablePickingPlugin, IntersectionData, PickableBundle,
+    PickingCamera, PickingCameraBundle, PickingEvent, PickingPlugin, PickingSettings,
+    PickingSystem, PickingUpdatePolicy, RayCastMethod,
+};
+
+/// The entities hit by every pick source, with their distance from the source.
+#[derive(Debug, Default, Resource)]
+pub struct TestHits(pub Vec<(Entity, f32)>);
+
+/// Every [PickingEvent] sent so far.
+#[derive(Debug, Default, Resource)]
+pub struct EventLog(pub Vec<PickingEvent>);
+
+/// A hit testing backend that reports the entities in [TestHits] for every pick source.
+pub fn test_backend(test_hits: Res<TestHits>, mut pick_source_query: Query<&mut PickingCamera>) {
+    for mut pick_source in pick_source_query.iter_mut() {
+        let hits = test_hits
+            .0
+            .iter()
+            .map(|(entity, distance)| {
+                let position = Vec3::new(0.0, 0.0, -*distance);
+                (
+                    *entity,
+                    IntersectionData::new(position, Vec3::Z, *distance, None),
+                )
+            })
+            .collect();
+        replace_intersections(&mut pick_source, |_| true, hits);
+    }
+}
+
+fn record_events(mut events: EventReader<PickingEvent>, mut log: ResMut<EventLog>) {
+    log.0.extend(events.iter().cloned());
+}
+
+/// An app with the [PickingPlugin] and [InteractablePickingPlugin], driven by [test_backend].
+pub fn app() -> App {
+    let mut app = app_with_raycast(PickingUpdatePolicy::OnEvent);
+    app.init_resource::<TestHits>().add_system_to_stage(
+        CoreStage::First,
+        test_backend
+            .label(PickingSystem::Backend)
+            .after(PickingSystem::UpdateIntersections),
+    );
+    app
+}
+
+/// An app with the [PickingPlugin] and [InteractablePickingPlugin], where only the mesh ray cast
+/// finds intersections, according to `update`.
+pub fn app_with_raycast(update: PickingUpdatePolicy) -> App {
+    let mut app = App::new();
+    app.init_resource::<Time>()
+        .add_plugin(TransformPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(InputPlugin)
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(AssetPlugin)
+        .add_asset::<Mesh>()
+        .add_asset::<Image>()
+        .insert_resource(PickingSettings { update })
+        .add_plugin(PickingPlugin)
+        .add_plugin(InteractablePickingPlugin)
+        .init_resource::<EventLog>()
+        .add_system_to_stage(CoreStage::PostUpdate, camera_system::<Projection>)
+        .add_system_to_stage(CoreStage::Last, record_events);
+    app
+}
+
+/// Spawns a pick source with its cursor at `cursor`, in logical pixels.
+pub fn spawn_source(app: &mut App, cursor: Vec2) -> Entity {
+    let mut pick_source = PickingCamera::new();
+    pick_source.cast_method = RayCastMethod::Screenspace(cursor);
+    app.world.spawn().insert(pick_source).id()
+}
+
+/// Adds a primary window, `size` logical pixels large with `scale_factor` physical pixels per
+/// logical pixel, and a camera that renders to it and picks with the cursor. The camera's target
+/// size is known after the next frame.
+pub fn spawn_window_camera(app: &mut App, size: Vec2, scale_factor: f64) -> Entity {
+    spawn_window(app, size, scale_factor);
+    spawn_cursor_camera(app, None)
+}
+
+/// Adds a primary window, `size` logical pixels large with `scale_factor` physical pixels per
+/// logical pixel.
+pub fn spawn_window(app: &mut App, size: Vec2, scale_factor: f64) {
+    let physical_size = (size * scale_factor as f32).as_uvec2();
+    let window = Window::new(
+        WindowId::primary(),
+        &WindowDescriptor::default(),
+        physical_size.x,
+        physical_size.y,
+        scale_factor,
+        None,
+        None,
+    );
+    app.world.resource_mut::<Windows>().add(window);
+}
+
+/// Adds a camera that renders to the primary window, or the part of it covered by `viewport`, and
+/// picks with the cursor. The camera's target size is known after the next frame.
+pub fn spawn_cursor_camera(app: &mut App, viewport: Option<Viewport>) -> Entity {
+    app.world
+        .spawn()
+        .insert_bundle(Camera3dBundle {
+            camera: Camera {
+                viewport,
+                ..default()
+            },
+            ..default()
+        })
+        .insert_bundle(PickingCameraBundle::default())
+        .id()
+}
+
+/// Moves the cursor in the primary window, in logical pixels with the origin in the bottom left.
+pub fn move_window_cursor(app: &mut App, position: Vec2) {
+    app.world
+        .resource_mut::<Events<CursorMoved>>()
+        .send(CursorMoved {
+            id: WindowId::primary(),
+            position,
+        });
+}
+
+/// Moves the cursor of the pick source spawned with [spawn_source].
+pub fn move_cursor(app: &mut App, source: Entity, cursor: Vec2) {
+    app.world
+        .get_mut::<PickingCamera>(source)
+        .unwrap()
+        .cast_method = RayCastMethod::Screenspace(cursor);
+}
+
+pub fn spawn_pickable(app: &mut App) -> Entity {
+    app.world
+        .spawn()
+        .insert_bundle(PickableBundle::default())
+        .id()
+}
+
+/// Sets the entities under the pointer, nearest first.
+pub fn hover(app: &mut App, entities: &[Entity]) {
+    app.world.resource_mut::<TestHits>().0 = entities
+        .iter()
+        .enumerate()
+        .map(|(i, entity)| (*entity, i as f32 + 1.0))
+        .collect();
+}
+
+/// Presses the left mouse button and runs a frame.
+pub fn press(app: &mut App) {
+    app.world
+        .resource_mut::<Input<MouseButton>>()
+        .press(MouseButton::Left);
+    app.update();
+}
+
+/// Releases the left mouse button and runs a frame.
+pub fn release(app: &mut App) {
+    app.world
+        .resource_mut::<Input<MouseButton>>()
+        .release(MouseButton::Left);
+    app.update();
+}
+
+/// Takes the [PickingEvent]s sent since the last call.
+pub fn take_events(app: &mut App) -> Vec<PickingEvent> {
+    std::mem::take(&mut app.world.resource_mut::<EventLog>().0)
+}
+
+pub fn intersected_entities(app: &App, source: Entity) -> Vec<Entity> {
+    app.world
+        .get::<PickingCamera>(source)
+        .unwrap()
+        .intersections()
+        .iter()
+        .map(|(entity, _)| *entity)
+        .collect()
+}
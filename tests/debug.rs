@@ -0,0 +1,26 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_mod_picking::{DebugCursor, DebugCursorPickingPlugin};
+use common::*;
+
+#[test]
+fn debug_cursor_is_never_picked() {
+    let mut app = app();
+    app.add_asset::<StandardMaterial>()
+        .add_plugin(DebugCursorPickingPlugin);
+    let source = spawn_source(&mut app, Vec2::ZERO);
+    let pickable = spawn_pickable(&mut app);
+    app.update();
+
+    // A backend that hits everything in front of the source, including the cursor sitting on the
+    // entity under it.
+    let mut cursor_query = app.world.query_filtered::<Entity, With<DebugCursor>>();
+    let cursor = cursor_query.single(&app.world);
+    let tail = app.world.get::<Children>(cursor).unwrap()[0];
+    app.world.resource_mut::<TestHits>().0 = vec![(cursor, 1.0), (tail, 1.5), (pickable, 2.0)];
+    for _ in 0..3 {
+        app.update();
+        assert_eq!(intersected_entities(&app, source), vec![pickable]);
+    }
+}
@@ -0,0 +1,42 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_mod_picking::{DragSettings, PickingEvent};
+use common::*;
+
+/// Presses on an entity, moves the cursor `distance` logical pixels, and releases it in the same
+/// frame, before a drag can start. Returns `true` if the entity was clicked.
+fn click_with_drift(scale_factor: f64, distance: f32) -> bool {
+    let mut app = app();
+    spawn_window_camera(&mut app, Vec2::new(800.0, 600.0), scale_factor);
+    let entity = spawn_pickable(&mut app);
+    app.update();
+
+    let start = Vec2::new(100.0, 100.0);
+    move_window_cursor(&mut app, start);
+    hover(&mut app, &[entity]);
+    app.update();
+    press(&mut app);
+    move_window_cursor(&mut app, start + Vec2::new(distance, 0.0));
+    release(&mut app);
+
+    take_events(&mut app).iter().any(|event| {
+        matches!(event, PickingEvent::Clicked { entity: clicked, .. } if *clicked == entity)
+    })
+}
+
+#[test]
+fn drift_below_the_drag_threshold_clicks() {
+    let threshold = DragSettings::default().threshold;
+    assert!(click_with_drift(1.0, threshold - 0.1));
+    assert!(!click_with_drift(1.0, threshold + 0.1));
+}
+
+#[test]
+fn drag_threshold_is_in_logical_pixels() {
+    // On a HiDPI window, drifting just under the threshold covers twice as many physical pixels,
+    // and still clicks.
+    let threshold = DragSettings::default().threshold;
+    assert!(click_with_drift(2.0, threshold - 0.1));
+    assert!(!click_with_drift(2.0, threshold + 0.1));
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use bevy::{prelude::*, render::camera::Projection};
+use bevy_mod_picking::{
+    apply_frustum_clipping, replace_intersections, ClipToFrustum, IntersectionData, PickDepthBias,
+    PickableBundle, PickableHierarchy, PickingCamera,
+};
+use common::*;
+
+#[test]
+fn depth_bias_applies_to_the_retargeted_root() {
+    let mut app = app();
+    let source = spawn_source(&mut app, Vec2::ZERO);
+    let mut child = None;
+    let root = app
+        .world
+        .spawn()
+        .insert_bundle(PickableBundle::default())
+        .insert(PickableHierarchy)
+        .insert(PickDepthBias(1.0))
+        .with_children(|parent| {
+            child = Some(parent.spawn().insert(Handle::<Mesh>::default()).id());
+        })
+        .id();
+    let child = child.unwrap();
+    let other = spawn_pickable(&mut app);
+
+    // The root is only hit through its child, which has no bias of its own. Sorting before
+    // retargeting would put `other` in front.
+    app.world.resource_mut::<TestHits>().0 = vec![(other, 1.5), (child, 2.0), (child, 4.0)];
+    app.update();
+    app.update();
+
+    assert_eq!(intersected_entities(&app, source), vec![root, other]);
+}
+
+/// Runs [apply_frustum_clipping] for a camera at the origin looking down -Z, with hits straight
+/// ahead at `distances`, and returns the distances of the hits that are kept.
+fn clip(projection: Projection, distances: &[f32]) -> Vec<f32> {
+    let mut world = World::new();
+    let mut pick_source = PickingCamera::new();
+    let hits = distances
+        .iter()
+        .enumerate()
+        .map(|(i, distance)| {
+            let position = Vec3::new(0.0, 0.0, -distance);
+            let intersection = IntersectionData::new(position, Vec3::Z, *distance, None);
+            (Entity::from_raw(i as u32), intersection)
+        })
+        .collect();
+    replace_intersections(&mut pick_source, |_| true, hits);
+    let source = world
+        .spawn()
+        .insert(pick_source)
+        .insert(projection)
+        .insert(GlobalTransform::default())
+        .insert(ClipToFrustum)
+        .id();
+
+    SystemStage::single(apply_frustum_clipping).run(&mut world);
+
+    world
+        .get::<PickingCamera>(source)
+        .unwrap()
+        .intersections()
+        .iter()
+        .map(|(_, intersection)| intersection.distance())
+        .collect()
+}
+
+#[test]
+fn frustum_clipping_perspective() {
+    let projection = PerspectiveProjection {
+        near: 0.1,
+        far: 100.0,
+        ..default()
+    };
+    let kept = clip(projection.into(), &[0.05, 0.1, 50.0, 150.0]);
+    assert_eq!(kept, vec![0.1, 50.0]);
+}
+
+#[test]
+fn frustum_clipping_orthographic() {
+    let projection = OrthographicProjection {
+        near: 1.0,
+        far: 100.0,
+        ..default()
+    };
+    let kept = clip(projection.into(), &[0.5, 1.0, 50.0, 150.0]);
+    assert_eq!(kept, vec![1.0, 50.0]);
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use bevy::prelude::*;
+use bevy_mod_picking::{CustomHighlightPlugin, Selection, StandardMaterialHighlight};
+use common::*;
+
+/// How many entities had their material handle changed in the last frame.
+#[derive(Default, Resource)]
+struct MaterialChanges(usize);
+
+fn count_material_changes(
+    mut changes: ResMut<MaterialChanges>,
+    query: Query<(), Changed<Handle<StandardMaterial>>>,
+) {
+    changes.0 = query.iter().count();
+}
+
+#[test]
+fn unchanged_highlight_state_doesnt_touch_the_material() {
+    let mut app = app();
+    app.add_asset::<StandardMaterial>()
+        .add_plugin(CustomHighlightPlugin(StandardMaterialHighlight))
+        .init_resource::<MaterialChanges>()
+        .add_system_to_stage(CoreStage::Last, count_material_changes);
+    spawn_source(&mut app, Vec2::ZERO);
+    let material = app
+        .world
+        .resource_mut::<Assets<StandardMaterial>>()
+        .add(Color::WHITE.into());
+    let entity = spawn_pickable(&mut app);
+    app.world.entity_mut(entity).insert(material);
+    app.update();
+    app.update();
+    assert_eq!(app.world.resource::<MaterialChanges>().0, 0);
+
+    hover(&mut app, &[entity]);
+    app.update();
+    assert_eq!(app.world.resource::<MaterialChanges>().0, 1);
+
+    // Triggers highlighting for the entity without changing its highlight state.
+    app.world
+        .get_mut::<Selection>(entity)
+        .unwrap()
+        .set_selected(false);
+    app.update();
+    assert_eq!(app.world.resource::<MaterialChanges>().0, 0);
+}
@@ -0,0 +1,62 @@
+mod common;
+
+use bevy::{ecs::system::SystemState, prelude::*};
+use bevy_mod_picking::{PickableBundle, PickingCamera, PickingRaycast, PickingUpdatePolicy};
+use common::*;
+
+fn spawn_cube(app: &mut App, mesh: &Handle<Mesh>, translation: Vec3) -> Entity {
+    app.world
+        .spawn()
+        .insert_bundle(PbrBundle {
+            mesh: mesh.clone(),
+            transform: Transform::from_translation(translation),
+            ..Default::default()
+        })
+        .insert_bundle(PickableBundle::default())
+        .id()
+}
+
+#[test]
+fn on_demand_cast_matches_pick_source_cast() {
+    let mut app = app_with_raycast(PickingUpdatePolicy::EveryFrame);
+    let mesh = app
+        .world
+        .resource_mut::<Assets<Mesh>>()
+        .add(Mesh::from(shape::Cube { size: 1.0 }));
+    let near = spawn_cube(&mut app, &mesh, Vec3::ZERO);
+    let far = spawn_cube(&mut app, &mesh, Vec3::new(0.0, 0.0, -3.0));
+    spawn_cube(&mut app, &mesh, Vec3::new(10.0, 0.0, 0.0));
+    let source = app
+        .world
+        .spawn()
+        .insert(PickingCamera::new_transform_empty())
+        .insert_bundle(TransformBundle::from(Transform::from_xyz(0.0, 0.0, 5.0)))
+        .id();
+    // Global transforms are propagated at the end of the first frame.
+    app.update();
+    app.update();
+
+    let pick_source = app.world.get::<PickingCamera>(source).unwrap();
+    let ray = pick_source.ray().unwrap();
+    let expected: Vec<_> = pick_source
+        .intersections()
+        .iter()
+        .map(|(entity, intersection)| (*entity, intersection.distance()))
+        .collect();
+    assert_eq!(
+        expected
+            .iter()
+            .map(|(entity, _)| *entity)
+            .collect::<Vec<_>>(),
+        vec![near, far]
+    );
+
+    let mut state = SystemState::<PickingRaycast>::new(&mut app.world);
+    let raycast = state.get_mut(&mut app.world);
+    let actual: Vec<_> = raycast
+        .cast(ray)
+        .iter()
+        .map(|(entity, intersection)| (*entity, intersection.distance()))
+        .collect();
+    assert_eq!(actual, expected);
+}
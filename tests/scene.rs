@@ -0,0 +1,59 @@
+mod common;
+
+use bevy::{
+    ecs::{entity::EntityMap, reflect::AppTypeRegistry},
+    prelude::*,
+    scene::DynamicScene,
+    window::CursorIcon,
+};
+use bevy_mod_picking::{
+    add_pickable_meshes, ClipToFrustum, CursorMotion, HoverCursorIcon, HoverCursorIconPlugin,
+    MaxPickDistance, Pickable, PickableDescendant, PickableHierarchy, PickableMesh,
+};
+use common::*;
+
+#[test]
+fn picking_components_survive_a_scene_round_trip() {
+    let mut app = app();
+    app.add_plugin(HoverCursorIconPlugin);
+    let root = app
+        .world
+        .spawn()
+        .insert(Pickable)
+        .insert(PickableHierarchy)
+        .insert(HoverCursorIcon(CursorIcon::Hand))
+        .id();
+    let descendant = app.world.spawn().insert(PickableDescendant { root }).id();
+    let camera = app
+        .world
+        .spawn()
+        .insert(MaxPickDistance(10.0))
+        .insert(ClipToFrustum)
+        .insert(CursorMotion::default())
+        .id();
+
+    let registry = app.world.resource::<AppTypeRegistry>().clone();
+    let scene = DynamicScene::from_world(&app.world, &registry);
+    let mut world = World::new();
+    world.insert_resource(registry);
+    let mut entity_map = EntityMap::default();
+    scene.write_to_world(&mut world, &mut entity_map).unwrap();
+    let root = entity_map.get(root).unwrap();
+    let descendant = entity_map.get(descendant).unwrap();
+    let camera = entity_map.get(camera).unwrap();
+
+    assert!(world.get::<Pickable>(root).is_some());
+    assert!(world.get::<PickableHierarchy>(root).is_some());
+    assert!(world.get::<HoverCursorIcon>(root).is_some());
+    assert_eq!(
+        world.get::<PickableDescendant>(descendant).unwrap().root,
+        root
+    );
+    assert_eq!(world.get::<MaxPickDistance>(camera).unwrap().0, 10.0);
+    assert!(world.get::<ClipToFrustum>(camera).is_some());
+    assert!(world.get::<CursorMotion>(camera).is_some());
+
+    // The ray cast component that can't be reflected is added back when the scene is spawned.
+    SystemStage::single(add_pickable_meshes).run(&mut world);
+    assert!(world.get::<PickableMesh>(root).is_some());
+}
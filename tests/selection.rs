@@ -0,0 +1,191 @@
+mod common;
+
+use bevy::{prelude::*, ui::FocusPolicy};
+use bevy_mod_picking::{DragSettings, NoDeselect, Selection, SelectionCommand, SelectionGroup};
+use common::*;
+
+fn selected(app: &App, entity: Entity) -> bool {
+    app.world.get::<Selection>(entity).unwrap().selected()
+}
+
+fn select(app: &mut App, entity: Entity) {
+    app.world
+        .get_mut::<Selection>(entity)
+        .unwrap()
+        .set_selected(true);
+}
+
+#[test]
+fn click_selects_on_release() {
+    let mut app = app();
+    spawn_source(&mut app, Vec2::new(100.0, 100.0));
+    let a = spawn_pickable(&mut app);
+    hover(&mut app, &[a]);
+
+    press(&mut app);
+    assert!(!selected(&app, a));
+    release(&mut app);
+    assert!(selected(&app, a));
+}
+
+#[test]
+fn click_selects_the_nearest_of_several_hovered_entities() {
+    let mut app = app();
+    spawn_source(&mut app, Vec2::new(100.0, 100.0));
+    let far = spawn_pickable(&mut app);
+    let near = spawn_pickable(&mut app);
+    // Let the pointer hover through the near entity, so both are hovered.
+    app.world.entity_mut(near).insert(FocusPolicy::Pass);
+    hover(&mut app, &[near, far]);
+
+    press(&mut app);
+    release(&mut app);
+    assert!(selected(&app, near));
+    assert!(!selected(&app, far));
+}
+
+#[test]
+fn press_on_one_entity_and_release_on_another_selects_nothing() {
+    let mut app = app();
+    spawn_source(&mut app, Vec2::new(100.0, 100.0));
+    let a = spawn_pickable(&mut app);
+    let b = spawn_pickable(&mut app);
+    let c = spawn_pickable(&mut app);
+    select(&mut app, c);
+
+    hover(&mut app, &[a]);
+    press(&mut app);
+    hover(&mut app, &[b]);
+    app.update();
+    release(&mut app);
+
+    assert!(!selected(&app, a));
+    assert!(!selected(&app, b));
+    assert!(selected(&app, c));
+}
+
+#[test]
+fn dragging_from_empty_space_doesnt_deselect() {
+    let mut app = app();
+    let source = spawn_source(&mut app, Vec2::new(100.0, 100.0));
+    let a = spawn_pickable(&mut app);
+    select(&mut app, a);
+
+    press(&mut app);
+    move_cursor(&mut app, source, Vec2::new(200.0, 100.0));
+    app.update();
+    release(&mut app);
+
+    assert!(selected(&app, a));
+}
+
+#[test]
+fn dragging_an_entity_and_releasing_it_over_itself_selects_nothing() {
+    let mut app = app();
+    let source = spawn_source(&mut app, Vec2::new(100.0, 100.0));
+    let a = spawn_pickable(&mut app);
+    let b = spawn_pickable(&mut app);
+    select(&mut app, b);
+
+    hover(&mut app, &[a]);
+    press(&mut app);
+    move_cursor(&mut app, source, Vec2::new(200.0, 100.0));
+    app.update();
+    release(&mut app);
+
+    assert!(!selected(&app, a));
+    assert!(selected(&app, b));
+}
+
+#[test]
+fn clicking_empty_space_deselects() {
+    let mut app = app();
+    spawn_source(&mut app, Vec2::new(100.0, 100.0));
+    let a = spawn_pickable(&mut app);
+    select(&mut app, a);
+
+    press(&mut app);
+    assert!(selected(&app, a));
+    release(&mut app);
+
+    assert!(!selected(&app, a));
+}
+
+/// Presses on an entity, moves the cursor `distance` logical pixels, and releases it in the same
+/// frame, before a drag can start. Returns `true` if the entity was selected.
+fn select_with_drift(scale_factor: f64, distance: f32) -> bool {
+    let mut app = app();
+    spawn_window_camera(&mut app, Vec2::new(800.0, 600.0), scale_factor);
+    let entity = spawn_pickable(&mut app);
+    app.update();
+
+    let start = Vec2::new(100.0, 100.0);
+    move_window_cursor(&mut app, start);
+    hover(&mut app, &[entity]);
+    app.update();
+    press(&mut app);
+    move_window_cursor(&mut app, start + Vec2::new(distance, 0.0));
+    release(&mut app);
+
+    selected(&app, entity)
+}
+
+#[test]
+fn drift_below_the_drag_threshold_selects() {
+    let threshold = DragSettings::default().threshold;
+    assert!(select_with_drift(1.0, threshold - 0.1));
+    assert!(!select_with_drift(1.0, threshold + 0.1));
+}
+
+#[test]
+fn selection_drag_threshold_is_in_logical_pixels() {
+    let threshold = DragSettings::default().threshold;
+    assert!(select_with_drift(2.0, threshold - 0.1));
+    assert!(!select_with_drift(2.0, threshold + 0.1));
+}
+
+fn spawn_in_group(app: &mut App, group: u32) -> Entity {
+    let entity = spawn_pickable(app);
+    app.world.entity_mut(entity).insert(SelectionGroup(group));
+    entity
+}
+
+#[test]
+fn selection_group_deselects_no_deselect_members() {
+    let mut app = app();
+    let a = spawn_in_group(&mut app, 0);
+    app.world.entity_mut(a).insert(NoDeselect);
+    let b = spawn_in_group(&mut app, 0);
+    app.update();
+
+    select(&mut app, a);
+    app.update();
+    assert!(selected(&app, a));
+
+    select(&mut app, b);
+    app.update();
+    assert!(!selected(&app, a));
+    assert!(selected(&app, b));
+}
+
+#[test]
+fn select_all_keeps_the_lowest_entity_of_each_group() {
+    let mut app = app();
+    let a = spawn_in_group(&mut app, 0);
+    let b = spawn_in_group(&mut app, 0);
+    let c = spawn_in_group(&mut app, 1);
+    let d = spawn_pickable(&mut app);
+    let e = spawn_pickable(&mut app);
+    app.update();
+
+    app.world
+        .resource_mut::<Events<SelectionCommand>>()
+        .send(SelectionCommand::SelectAll);
+    app.update();
+
+    assert!(selected(&app, a));
+    assert!(!selected(&app, b));
+    assert!(selected(&app, c));
+    assert!(selected(&app, d));
+    assert!(selected(&app, e));
+}
@@ -0,0 +1,92 @@
+mod common;
+
+use bevy::{prelude::*, utils::Instant, window::WindowId};
+use bevy_mod_picking::{PickingSettings, PickingSystem, PickingUpdatePolicy, UpdatePicksCriteria};
+use common::*;
+use std::time::Duration;
+
+/// How many times the backends ran.
+#[derive(Default, Resource)]
+struct Casts(usize);
+
+fn count_casts(mut casts: ResMut<Casts>) {
+    casts.0 += 1;
+}
+
+fn app_with_policy(update: PickingUpdatePolicy) -> App {
+    let mut app = app();
+    app.insert_resource(PickingSettings { update })
+        .init_resource::<Casts>()
+        .add_system_set_to_stage(
+            CoreStage::First,
+            SystemSet::new()
+                .with_run_criteria(UpdatePicksCriteria)
+                .with_system(
+                    count_casts
+                        .label(PickingSystem::Backend)
+                        .after(PickingSystem::UpdateIntersections),
+                ),
+        );
+    app.world
+        .resource_mut::<Time>()
+        .update_with_instant(Instant::now());
+    app
+}
+
+/// Runs a frame that took `delta`.
+fn step(app: &mut App, delta: Duration) {
+    let mut time = app.world.resource_mut::<Time>();
+    let now = time.last_update().unwrap() + delta;
+    time.update_with_instant(now);
+    app.update();
+}
+
+fn casts(app: &App) -> usize {
+    app.world.resource::<Casts>().0
+}
+
+#[test]
+fn interval_casts_once_per_interval() {
+    let mut app = app_with_policy(PickingUpdatePolicy::Interval(Duration::from_millis(120)));
+    spawn_source(&mut app, Vec2::ZERO);
+
+    for _ in 0..11 {
+        step(&mut app, Duration::from_millis(50));
+    }
+    assert_eq!(casts(&app), 4);
+
+    // A long frame doesn't catch up with the casts it missed.
+    step(&mut app, Duration::from_millis(500));
+    assert_eq!(casts(&app), 5);
+}
+
+#[test]
+fn on_event_only_casts_after_input() {
+    let mut app = app_with_policy(PickingUpdatePolicy::OnEvent);
+    spawn_source(&mut app, Vec2::ZERO);
+
+    for _ in 0..5 {
+        step(&mut app, Duration::from_millis(50));
+    }
+    assert_eq!(casts(&app), 0);
+
+    app.world
+        .resource_mut::<Events<CursorMoved>>()
+        .send(CursorMoved {
+            id: WindowId::primary(),
+            position: Vec2::new(10.0, 10.0),
+        });
+    step(&mut app, Duration::from_millis(50));
+    step(&mut app, Duration::from_millis(50));
+    assert_eq!(casts(&app), 1);
+}
+
+#[test]
+fn every_frame_casts_every_frame() {
+    let mut app = app_with_policy(PickingUpdatePolicy::EveryFrame);
+
+    for _ in 0..3 {
+        step(&mut app, Duration::from_millis(50));
+    }
+    assert_eq!(casts(&app), 3);
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use bevy::{prelude::*, render::camera::Viewport};
+use bevy_mod_picking::Ray3d;
+use common::*;
+
+fn viewport(position: UVec2, size: UVec2) -> Option<Viewport> {
+    Some(Viewport {
+        physical_position: position,
+        physical_size: size,
+        ..default()
+    })
+}
+
+/// The direction of the ray cast through `cursor`, in logical pixels, by `camera`.
+fn ray_direction(app: &App, camera: Entity, cursor: Vec2) -> Vec3 {
+    let transform = app.world.get::<GlobalTransform>(camera).unwrap();
+    let camera = app.world.get::<Camera>(camera).unwrap();
+    Ray3d::from_screenspace(cursor, camera, transform)
+        .unwrap()
+        .direction()
+}
+
+fn assert_forward(direction: Vec3) {
+    assert!(
+        direction.abs_diff_eq(Vec3::NEG_Z, 1e-4),
+        "{} doesn't point forward",
+        direction
+    );
+}
+
+fn split_screen(app: &mut App, scale_factor: f64) -> (Entity, Entity) {
+    spawn_window(app, Vec2::new(800.0, 600.0), scale_factor);
+    let half = (Vec2::new(400.0, 600.0) * scale_factor as f32).as_uvec2();
+    let left = spawn_cursor_camera(app, viewport(UVec2::ZERO, half));
+    let right = spawn_cursor_camera(app, viewport(UVec2::new(half.x, 0), half));
+    app.update();
+    (left, right)
+}
+
+#[test]
+fn split_screen_rays_go_through_the_cursor_in_each_viewport() {
+    let mut app = app();
+    let (left, right) = split_screen(&mut app, 1.0);
+
+    assert_forward(ray_direction(&app, left, Vec2::new(200.0, 300.0)));
+    assert_forward(ray_direction(&app, right, Vec2::new(600.0, 300.0)));
+}
+
+#[test]
+fn split_screen_only_picks_in_the_viewport_under_the_cursor() {
+    let mut app = app();
+    let (left, right) = split_screen(&mut app, 1.0);
+    let entity = spawn_pickable(&mut app);
+    hover(&mut app, &[entity]);
+
+    move_window_cursor(&mut app, Vec2::new(200.0, 300.0));
+    app.update();
+    assert_eq!(intersected_entities(&app, left), vec![entity]);
+    assert!(intersected_entities(&app, right).is_empty());
+
+    move_window_cursor(&mut app, Vec2::new(600.0, 300.0));
+    app.update();
+    assert!(intersected_entities(&app, left).is_empty());
+    assert_eq!(intersected_entities(&app, right), vec![entity]);
+}
+
+#[test]
+fn hidpi_cursor_is_converted_with_logical_sizes() {
+    let mut app = app();
+    let camera = spawn_window_camera(&mut app, Vec2::new(800.0, 600.0), 2.0);
+    app.update();
+
+    assert_forward(ray_direction(&app, camera, Vec2::new(400.0, 300.0)));
+    // The right edge of the window is at NDC x = 1, where the ray leaves at the edge of the
+    // horizontal field of view.
+    let direction = ray_direction(&app, camera, Vec2::new(800.0, 300.0));
+    let half_fov = PerspectiveProjection::default().fov / 2.0;
+    let expected = half_fov.tan() * 800.0 / 600.0;
+    assert!((direction.x / -direction.z - expected).abs() < 1e-4);
+    assert!(direction.y.abs() < 1e-4);
+}
+
+#[test]
+fn hidpi_split_screen_rays_go_through_the_cursor_in_each_viewport() {
+    let mut app = app();
+    let (left, right) = split_screen(&mut app, 2.0);
+
+    assert_forward(ray_direction(&app, left, Vec2::new(200.0, 300.0)));
+    assert_forward(ray_direction(&app, right, Vec2::new(600.0, 300.0)));
+}